@@ -0,0 +1,186 @@
+use anyhow::Result;
+use console::style;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use solana_vanity::keygen::SearchMode;
+use solana_vanity::pattern::PatternMatcher;
+use solana_vanity::vanity::{PatternType, VanityGenerator, VanityOptions};
+
+/// How long a run calibrates when this machine has no cached rate yet
+pub const CALIBRATION_TIME: Duration = Duration::from_millis(500);
+
+/// Measured attempts per second of a single worker thread, by search mode and pattern type
+///
+/// Stored as JSON in the user's cache directory so only the first run on a machine
+/// (or `benchmark`) pays for a calibration.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RateCache {
+    rates: BTreeMap<String, f64>,
+}
+
+impl RateCache {
+    /// Load the cache, starting empty if it is missing or unreadable
+    pub fn load(path: &PathBuf) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &PathBuf) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, options: &VanityOptions) -> Option<f64> {
+        self.rates.get(&rate_key(options)).copied()
+    }
+
+    pub fn insert(&mut self, options: &VanityOptions, rate: f64) {
+        self.rates.insert(rate_key(options), rate);
+    }
+}
+
+/// Where the rate cache lives: `$XDG_CACHE_HOME/solana-vanity` or `~/.cache/solana-vanity`
+pub fn cache_path() -> Option<PathBuf> {
+    let cache_dir = std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+    Some(cache_dir.join("solana-vanity").join("rates.json"))
+}
+
+/// Rates depend on how candidates are generated and matched, not on the exact pattern
+///
+/// Case sensitivity and whether the prefix pre-filter applies change the cost of each
+/// match, so they are part of the key. Debug builds are an order of magnitude slower,
+/// so they keep their own rates.
+fn rate_key(options: &VanityOptions) -> String {
+    let mode = match options.mode {
+        SearchMode::Keypair => "keypair",
+        SearchMode::Mnemonic => "mnemonic",
+        SearchMode::Seed { .. } => "seed",
+        SearchMode::Pda { .. } => "pda",
+    };
+    let case = if options.case_sensitive { "case-sensitive" } else { "case-insensitive" };
+    let prefiltered = PatternMatcher::new(options).is_ok_and(|matcher| matcher.has_prefilter());
+    let filter = if prefiltered { "prefiltered" } else { "unfiltered" };
+    let profile = if cfg!(debug_assertions) { "debug" } else { "release" };
+    format!("{}/{}/{:?}/{}/{}", profile, mode, options.pattern_type, case, filter)
+}
+
+/// Per-thread attempts per second for `options`, and whether it came from the cache
+///
+/// Without a cached rate, the generator is calibrated for `CALIBRATION_TIME` and the
+/// result is cached for next time.
+pub fn per_thread_rate(generator: &VanityGenerator, options: &VanityOptions) -> Result<(f64, bool)> {
+    let path = cache_path();
+    let mut cache = path.as_ref().map(RateCache::load).unwrap_or_default();
+    if let Some(rate) = cache.get(options) {
+        return Ok((rate, true));
+    }
+
+    let rate = generator.measure_attempt_rate(options, CALIBRATION_TIME)?;
+    cache.insert(options, rate);
+    if let Some(path) = &path {
+        // Failing to cache only means calibrating again next time
+        let _ = cache.save(path);
+    }
+    Ok((rate, false))
+}
+
+/// Measure every search mode on this machine and cache the rates for later estimates
+pub fn run(pattern_type: &PatternType, case_sensitive: bool, duration: Duration, threads: usize) -> Result<()> {
+    let thread_count = if threads == 0 { num_cpus::get() } else { threads };
+    let generator = VanityGenerator::new();
+    let any_pubkey = solana_sdk::system_program::id();
+    let modes = [
+        ("Keypair", SearchMode::Keypair),
+        ("BIP39 mnemonic", SearchMode::Mnemonic),
+        ("create_with_seed", SearchMode::Seed { base: any_pubkey, owner: any_pubkey, seed_prefix: String::new() }),
+        ("PDA", SearchMode::Pda { program_id: any_pubkey, seeds: vec![b"vanity".to_vec()] }),
+    ];
+
+    println!("{}", style("🦀 Solana Vanity Benchmark").bold().cyan());
+    println!("  Pattern type: {}", style(format!("{:?}", pattern_type)).green());
+    println!("  Case sensitive: {}", style(case_sensitive.to_string()).green());
+    println!("  Threads: {}", style(thread_count.to_string()).green());
+    println!();
+
+    let cache_path = cache_path();
+    let mut cache = cache_path.as_ref().map(RateCache::load).unwrap_or_default();
+    for (name, mode) in modes {
+        // An unlikely pattern, so the loop measures the cost of misses
+        let options = VanityOptions::builder()
+            .pattern(if let PatternType::Glob = pattern_type { "AB?D" } else { "ABCD" }, 1)
+            .pattern_type(pattern_type.clone())
+            .case_sensitive(case_sensitive)
+            .max_attempts(u64::MAX)
+            .max_time(duration)
+            .mode(mode)
+            .build()?;
+        let rate = generator.measure_attempt_rate(&options, duration)?;
+        cache.insert(&options, rate);
+        println!("  {:<18}{}", format!("{}:", name), style(format!("{:.0} attempts/sec per thread, ~{:.0} total",
+            rate, rate * thread_count as f64)).green());
+    }
+
+    if let Some(cache_path) = cache_path {
+        cache.save(&cache_path)?;
+        println!();
+        println!("{}", style(format!("Rates cached in: {}", cache_path.display())).green());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_vanity::vanity::PatternTarget;
+
+    fn options(mode: SearchMode) -> VanityOptions {
+        VanityOptions {
+            patterns: vec![PatternTarget::new("AB", 1)],
+            pattern_type: PatternType::StartsWith,
+            case_sensitive: true,
+            suffix: None,
+            max_attempts: 1000000,
+            max_time: Duration::from_secs(60),
+            mode,
+        }
+    }
+
+    #[test]
+    fn test_rate_cache_round_trip() {
+        let path = std::env::temp_dir().join(format!("solana-vanity-{}-cache", std::process::id())).join("rates.json");
+        let keypair = options(SearchMode::Keypair);
+        let mnemonic = options(SearchMode::Mnemonic);
+
+        let mut cache = RateCache::load(&path);
+        assert!(cache.get(&keypair).is_none());
+        cache.insert(&keypair, 40_000.0);
+        cache.save(&path).unwrap();
+
+        let cache = RateCache::load(&path);
+        assert_eq!(cache.get(&keypair), Some(40_000.0));
+        assert!(cache.get(&mnemonic).is_none());
+
+        // Matching costs differ with case sensitivity and with the prefix pre-filter
+        let case_insensitive = VanityOptions {
+            case_sensitive: false,
+            ..options(SearchMode::Keypair)
+        };
+        let unfiltered = VanityOptions {
+            patterns: vec![PatternTarget::new("1A", 1)],
+            ..options(SearchMode::Keypair)
+        };
+        assert!(cache.get(&case_insensitive).is_none());
+        assert!(cache.get(&unfiltered).is_none());
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,50 @@
+use thiserror::Error;
+
+/// Everything that can go wrong when configuring or running a search
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum VanityError {
+    #[error("No patterns given")]
+    NoPatterns,
+
+    #[error("Invalid pattern '{pattern}': {message}")]
+    InvalidPattern { pattern: String, message: String },
+
+    /// Settings that cannot be used together
+    #[error("{0}")]
+    InvalidOptions(String),
+
+    /// The search mode's parameters can never produce a valid address
+    #[error("{0}")]
+    InvalidMode(String),
+
+    /// Stored key material that cannot be turned back into a keypair
+    #[error("{0}")]
+    InvalidKeyMaterial(String),
+
+    /// A keystore that cannot be sealed or opened
+    #[error("{0}")]
+    Keystore(String),
+
+    /// An output file or keypair directory that cannot be used
+    #[error("{0}")]
+    Output(String),
+
+    /// Writing would replace previously saved results
+    #[error("{0} already exists")]
+    OutputExists(String),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    #[error("Failed to start worker threads: {0}")]
+    ThreadPool(#[from] rayon::ThreadPoolBuildError),
+
+    #[error("Search task failed: {0}")]
+    Task(#[from] tokio::task::JoinError),
+}
+
+pub type Result<T, E = VanityError> = std::result::Result<T, E>;
@@ -0,0 +1,389 @@
+use bip39::{Language, Mnemonic, MnemonicType, Seed};
+use serde::{Deserialize, Serialize};
+use solana_sdk::derivation_path::DerivationPath;
+use solana_sdk::pubkey::{Pubkey, MAX_SEEDS, MAX_SEED_LEN};
+use std::borrow::Cow;
+use zeroize::Zeroizing;
+
+use crate::error::{Result, VanityError};
+use crate::secret::SecretString;
+use solana_sdk::signature::{keypair_from_seed_and_derivation_path, Keypair, Signer};
+
+/// Derivation path used by Phantom, Solflare and most other Solana wallets
+pub const MNEMONIC_DERIVATION_PATH: &str = "m/44'/501'/0'/0'";
+
+/// Longest seed prefix that still leaves room for any attempt number
+pub const MAX_SEED_PREFIX_LEN: usize = MAX_SEED_LEN - 20;
+
+/// Most fixed PDA seeds allowed, leaving room for the nonce and bump seeds
+pub const MAX_PDA_SEEDS: usize = MAX_SEEDS - 2;
+
+/// How candidate addresses are produced
+#[derive(Debug, Clone, Default)]
+pub enum SearchMode {
+    /// A fresh random keypair per attempt
+    #[default]
+    Keypair,
+    /// A random 12-word BIP39 mnemonic per attempt, derived along `MNEMONIC_DERIVATION_PATH`
+    ///
+    /// Much slower than `Keypair`, since every attempt runs the BIP39 seed function.
+    Mnemonic,
+    /// `Pubkey::create_with_seed` addresses for a fixed base and owner
+    ///
+    /// Each attempt costs a single SHA-256. The seed is `seed_prefix` followed by the
+    /// attempt number, so the same settings always find the same addresses.
+    Seed {
+        base: Pubkey,
+        owner: Pubkey,
+        seed_prefix: String,
+    },
+    /// Program-derived addresses for `program_id`
+    ///
+    /// Each attempt appends the attempt number as a little-endian `u64` nonce seed to
+    /// `seeds` and takes the canonical bump from `Pubkey::find_program_address`.
+    Pda {
+        program_id: Pubkey,
+        seeds: Vec<Vec<u8>>,
+    },
+}
+
+impl SearchMode {
+    /// Check that every candidate this mode produces can actually be derived
+    pub fn validate(&self) -> Result<()> {
+        if let SearchMode::Seed { base, owner, seed_prefix } = self {
+            if seed_prefix.len() > MAX_SEED_PREFIX_LEN {
+                return Err(VanityError::InvalidMode(format!("Seed prefix must be at most {} bytes", MAX_SEED_PREFIX_LEN)));
+            }
+            Pubkey::create_with_seed(base, seed_prefix, owner)
+                .map_err(|e| VanityError::InvalidMode(format!("Invalid seed derivation: {}", e)))?;
+        }
+        if let SearchMode::Pda { seeds, .. } = self {
+            if seeds.len() > MAX_PDA_SEEDS {
+                return Err(VanityError::InvalidMode(format!("At most {} PDA seeds are allowed", MAX_PDA_SEEDS)));
+            }
+            if let Some(seed) = seeds.iter().find(|seed| seed.len() > MAX_SEED_LEN) {
+                return Err(VanityError::InvalidMode(format!(
+                    "PDA seed {} is longer than {} bytes",
+                    hex::encode(seed),
+                    MAX_SEED_LEN
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Generate the candidate address for the given attempt number
+    ///
+    /// Assumes the mode passed `validate`.
+    pub fn generate(&self, attempt: u64) -> Candidate {
+        match self {
+            SearchMode::Keypair => Candidate::Keypair(Keypair::new()),
+            SearchMode::Mnemonic => {
+                let mnemonic = Mnemonic::new(MnemonicType::Words12, Language::English);
+                let keypair = mnemonic_keypair(&mnemonic)
+                    .expect("hardened derivation from a 64-byte seed cannot fail");
+                Candidate::Mnemonic(keypair, mnemonic)
+            }
+            SearchMode::Seed { base, owner, seed_prefix } => {
+                let seed = format!("{}{}", seed_prefix, attempt);
+                let address = Pubkey::create_with_seed(base, &seed, owner)
+                    .expect("validated seed derivation cannot fail");
+                Candidate::Seed {
+                    address,
+                    base: *base,
+                    seed,
+                    owner: *owner,
+                }
+            }
+            SearchMode::Pda { program_id, seeds } => {
+                let nonce = attempt.to_le_bytes();
+                let mut seed_refs: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+                seed_refs.push(&nonce);
+                let (address, bump) = Pubkey::try_find_program_address(&seed_refs, program_id)
+                    .expect("some bump yields an off-curve address");
+                Candidate::Pda {
+                    address,
+                    program_id: *program_id,
+                    seeds: seeds.clone(),
+                    nonce: attempt,
+                    bump,
+                }
+            }
+        }
+    }
+
+    /// Whether results hold secrets that control the address, rather than public derivation inputs
+    pub fn has_secrets(&self) -> bool {
+        matches!(self, SearchMode::Keypair | SearchMode::Mnemonic)
+    }
+
+    /// CSV column names for the key material of results produced in this mode
+    pub fn key_columns(&self) -> &'static [&'static str] {
+        match self {
+            SearchMode::Keypair => &["private_key"],
+            SearchMode::Mnemonic => &["mnemonic", "derivation_path"],
+            SearchMode::Seed { .. } => &["base", "seed", "owner"],
+            SearchMode::Pda { .. } => &["program_id", "seeds", "nonce", "bump"],
+        }
+    }
+}
+
+/// A freshly generated address, before it is known whether it matches
+///
+/// Candidates that do not match are simply dropped: the ed25519 secret key and the
+/// BIP39 mnemonic both wipe their bytes on drop, so nothing of them outlives the attempt.
+pub enum Candidate {
+    Keypair(Keypair),
+    Mnemonic(Keypair, Mnemonic),
+    Seed {
+        address: Pubkey,
+        base: Pubkey,
+        seed: String,
+        owner: Pubkey,
+    },
+    Pda {
+        address: Pubkey,
+        program_id: Pubkey,
+        /// Fixed seeds, without the nonce
+        seeds: Vec<Vec<u8>>,
+        nonce: u64,
+        bump: u8,
+    },
+}
+
+impl Candidate {
+    pub fn pubkey(&self) -> Pubkey {
+        match self {
+            Candidate::Keypair(keypair) | Candidate::Mnemonic(keypair, _) => keypair.pubkey(),
+            Candidate::Seed { address, .. } | Candidate::Pda { address, .. } => *address,
+        }
+    }
+
+    /// Everything needed to use the address, encoded only once it has matched
+    pub fn into_key_material(self) -> KeyMaterial {
+        match self {
+            Candidate::Keypair(keypair) => {
+                let bytes = Zeroizing::new(keypair.to_bytes());
+                KeyMaterial::Keypair {
+                    private_key: bs58::encode(bytes.as_slice()).into_string().into(),
+                }
+            }
+            Candidate::Mnemonic(_, mnemonic) => KeyMaterial::Mnemonic {
+                mnemonic: mnemonic.into_phrase().into(),
+                derivation_path: MNEMONIC_DERIVATION_PATH.to_string(),
+            },
+            Candidate::Seed { base, seed, owner, .. } => KeyMaterial::Seed {
+                base: base.to_string(),
+                seed,
+                owner: owner.to_string(),
+            },
+            Candidate::Pda { program_id, seeds, nonce, bump, .. } => KeyMaterial::Pda {
+                program_id: program_id.to_string(),
+                seeds: seeds
+                    .iter()
+                    .map(Vec::as_slice)
+                    .chain([nonce.to_le_bytes().as_slice()])
+                    .map(hex::encode)
+                    .collect(),
+                nonce,
+                bump,
+            },
+        }
+    }
+}
+
+/// What is needed to use or recreate a found address
+///
+/// Serialized inline with the rest of the result, so keypair results keep their
+/// familiar `private_key` field. Secrets are wiped when the material is dropped.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeyMaterial {
+    Keypair {
+        private_key: SecretString,
+    },
+    /// A seed phrase that any standard wallet can import
+    Mnemonic {
+        mnemonic: SecretString,
+        derivation_path: String,
+    },
+    /// Inputs to `Pubkey::create_with_seed`; the base keypair signs for the address
+    Seed {
+        base: String,
+        seed: String,
+        owner: String,
+    },
+    /// Inputs to `Pubkey::create_program_address`; the program signs for the address
+    Pda {
+        program_id: String,
+        /// Hex-encoded seeds in order, ending with the nonce seed
+        seeds: Vec<String>,
+        nonce: u64,
+        bump: u8,
+    },
+}
+
+impl KeyMaterial {
+    /// Display label and value of each field, in CSV column order
+    pub fn fields(&self) -> Vec<(&'static str, Cow<'_, str>)> {
+        match self {
+            KeyMaterial::Keypair { private_key } => vec![("Private Key", private_key.expose().into())],
+            KeyMaterial::Mnemonic { mnemonic, derivation_path } => {
+                vec![("Mnemonic", mnemonic.expose().into()), ("Derivation", derivation_path.into())]
+            }
+            KeyMaterial::Seed { base, seed, owner } => {
+                vec![("Base", base.into()), ("Seed", seed.into()), ("Owner", owner.into())]
+            }
+            KeyMaterial::Pda { program_id, seeds, nonce, bump } => vec![
+                ("Program", program_id.into()),
+                ("Seeds", seeds.join(" ").into()),
+                ("Nonce", nonce.to_string().into()),
+                ("Bump", bump.to_string().into()),
+            ],
+        }
+    }
+
+    /// Recover the signing keypair for the address
+    pub fn to_keypair(&self) -> Result<Keypair> {
+        match self {
+            KeyMaterial::Keypair { private_key } => {
+                let bytes = Zeroizing::new(
+                    bs58::decode(private_key.expose())
+                        .into_vec()
+                        .map_err(|e| VanityError::InvalidKeyMaterial(format!("Invalid private key: {}", e)))?,
+                );
+                Keypair::from_bytes(&bytes).map_err(|e| VanityError::InvalidKeyMaterial(format!("Invalid keypair: {}", e)))
+            }
+            KeyMaterial::Mnemonic { mnemonic, derivation_path } => {
+                if derivation_path != MNEMONIC_DERIVATION_PATH {
+                    return Err(VanityError::InvalidKeyMaterial(format!("Unsupported derivation path: {}", derivation_path)));
+                }
+                let mnemonic = Mnemonic::from_phrase(mnemonic.expose(), Language::English)
+                    .map_err(|e| VanityError::InvalidKeyMaterial(format!("Invalid mnemonic: {}", e)))?;
+                mnemonic_keypair(&mnemonic)
+            }
+            KeyMaterial::Seed { base, .. } => {
+                Err(VanityError::InvalidKeyMaterial(format!(
+                    "Seed-derived addresses have no keypair; sign with the base key {}",
+                    base
+                )))
+            }
+            KeyMaterial::Pda { program_id, .. } => {
+                Err(VanityError::InvalidKeyMaterial(format!(
+                    "Program-derived addresses have no keypair; program {} signs for them",
+                    program_id
+                )))
+            }
+        }
+    }
+}
+
+/// Parse a fixed PDA seed: `pubkey:<base58>`, `hex:<bytes>`, or plain UTF-8 text
+pub fn parse_pda_seed(seed: &str) -> Result<Vec<u8>> {
+    if let Some(pubkey) = seed.strip_prefix("pubkey:") {
+        let pubkey: Pubkey = pubkey
+            .parse()
+            .map_err(|e| VanityError::InvalidMode(format!("Invalid pubkey seed '{}': {}", pubkey, e)))?;
+        Ok(pubkey.to_bytes().to_vec())
+    } else if let Some(bytes) = seed.strip_prefix("hex:") {
+        hex::decode(bytes).map_err(|e| VanityError::InvalidMode(format!("Invalid hex seed '{}': {}", bytes, e)))
+    } else {
+        Ok(seed.as_bytes().to_vec())
+    }
+}
+
+/// Derive the wallet keypair for a mnemonic with an empty passphrase
+fn mnemonic_keypair(mnemonic: &Mnemonic) -> Result<Keypair> {
+    let seed = Seed::new(mnemonic, "");
+    keypair_from_seed_and_derivation_path(seed.as_bytes(), Some(DerivationPath::new_bip44(Some(0), Some(0))))
+        .map_err(|e| VanityError::InvalidKeyMaterial(format!("Failed to derive keypair: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mnemonic_derivation_matches_wallets() {
+        // Well-known BIP39 test vector; Phantom shows this address for the first account
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let material = KeyMaterial::Mnemonic {
+            mnemonic: phrase.to_string().into(),
+            derivation_path: MNEMONIC_DERIVATION_PATH.to_string(),
+        };
+        let keypair = material.to_keypair().unwrap();
+        assert_eq!(keypair.pubkey().to_string(), "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk");
+    }
+
+    #[test]
+    fn test_seed_candidates_recreate_address() {
+        let base = Pubkey::new_unique();
+        let owner = solana_sdk::system_program::id();
+        let mode = SearchMode::Seed { base, owner, seed_prefix: "vanity-".to_string() };
+        mode.validate().unwrap();
+
+        let candidate = mode.generate(u64::MAX);
+        let address = candidate.pubkey();
+        let KeyMaterial::Seed { base: b, seed, owner: o } = candidate.into_key_material() else {
+            panic!("expected seed key material");
+        };
+        assert_eq!(seed, format!("vanity-{}", u64::MAX));
+        assert_eq!(Pubkey::create_with_seed(&b.parse().unwrap(), &seed, &o.parse().unwrap()).unwrap(), address);
+
+        // Different attempts give different addresses
+        assert_ne!(mode.generate(0).pubkey(), mode.generate(1).pubkey());
+
+        let too_long = SearchMode::Seed { base, owner, seed_prefix: "x".repeat(MAX_SEED_PREFIX_LEN + 1) };
+        assert!(too_long.validate().is_err());
+    }
+
+    #[test]
+    fn test_pda_candidates_recreate_address() {
+        let program_id = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        let mode = SearchMode::Pda {
+            program_id,
+            seeds: vec![parse_pda_seed("vault").unwrap(), parse_pda_seed(&format!("pubkey:{}", user)).unwrap()],
+        };
+        mode.validate().unwrap();
+
+        let candidate = mode.generate(7);
+        let address = candidate.pubkey();
+        let material = candidate.into_key_material();
+        assert_eq!(material.fields().len(), mode.key_columns().len());
+        assert!(material.to_keypair().is_err());
+        let KeyMaterial::Pda { program_id: p, seeds, nonce, bump } = material else {
+            panic!("expected PDA key material");
+        };
+        assert_eq!(p, program_id.to_string());
+        assert_eq!(nonce, 7);
+        assert_eq!(seeds.last().unwrap(), &hex::encode(7u64.to_le_bytes()));
+
+        // The recorded seeds and canonical bump recreate the address
+        let mut seed_bytes: Vec<Vec<u8>> = seeds.iter().map(|seed| hex::decode(seed).unwrap()).collect();
+        let seed_refs: Vec<&[u8]> = seed_bytes.iter().map(Vec::as_slice).collect();
+        assert_eq!(Pubkey::find_program_address(&seed_refs, &program_id), (address, bump));
+        seed_bytes.push(vec![bump]);
+        let seed_refs: Vec<&[u8]> = seed_bytes.iter().map(Vec::as_slice).collect();
+        assert_eq!(Pubkey::create_program_address(&seed_refs, &program_id).unwrap(), address);
+
+        let too_many = SearchMode::Pda { program_id, seeds: vec![vec![1]; MAX_PDA_SEEDS + 1] };
+        assert!(too_many.validate().is_err());
+        let too_long = SearchMode::Pda { program_id, seeds: vec![vec![1; MAX_SEED_LEN + 1]] };
+        assert!(too_long.validate().is_err());
+        assert_eq!(parse_pda_seed("hex:00ff").unwrap(), vec![0, 255]);
+        assert!(parse_pda_seed("hex:zz").is_err());
+    }
+
+    #[test]
+    fn test_candidate_key_material_round_trip() {
+        for mode in [SearchMode::Keypair, SearchMode::Mnemonic] {
+            let candidate = mode.generate(0);
+            let pubkey = candidate.pubkey();
+            let material = candidate.into_key_material();
+
+            assert_eq!(material.fields().len(), mode.key_columns().len());
+            assert_eq!(material.to_keypair().unwrap().pubkey(), pubkey);
+        }
+    }
+}
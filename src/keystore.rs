@@ -0,0 +1,236 @@
+use chacha20poly1305::aead::{Aead, NewAead, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use zeroize::Zeroizing;
+
+use crate::error::{Result, VanityError};
+use crate::vanity::VanityResult;
+
+/// scrypt cost used for new keystores: 2^15 rounds, 32 MiB of memory
+const DEFAULT_LOG_N: u8 = 15;
+const KDF_R: u32 = 8;
+const KDF_P: u32 = 1;
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+/// A found address whose key material is encrypted under a passphrase
+///
+/// Everything needed to search for or identify the address stays readable; only the
+/// key material is sealed. The public key is bound to the ciphertext as associated
+/// data, so entries cannot be swapped between addresses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeystoreEntry {
+    pub public_key: String,
+    pub pattern: String,
+    pub attempts: u64,
+    pub time_elapsed: Duration,
+    pub crypto: Crypto,
+}
+
+/// How an entry's key material was sealed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Crypto {
+    /// Always "scrypt"
+    pub kdf: String,
+    pub kdfparams: KdfParams,
+    /// Always "xchacha20poly1305"
+    pub cipher: String,
+    /// Hex-encoded 24-byte nonce
+    pub nonce: String,
+    /// Hex-encoded ciphertext of the JSON key material, including the 16-byte tag
+    pub ciphertext: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+    /// Hex-encoded salt
+    pub salt: String,
+}
+
+impl KdfParams {
+    fn derive_key(&self, passphrase: &str) -> Result<Zeroizing<[u8; KEY_LEN]>> {
+        let params = scrypt::Params::new(self.log_n, self.r, self.p, KEY_LEN)
+            .map_err(|e| VanityError::Keystore(format!("Invalid scrypt parameters: {}", e)))?;
+        let salt = decode_hex("salt", &self.salt)?;
+        let mut key = Zeroizing::new([0u8; KEY_LEN]);
+        scrypt::scrypt(passphrase.as_bytes(), &salt, &params, key.as_mut())
+            .map_err(|e| VanityError::Keystore(format!("Key derivation failed: {}", e)))?;
+        Ok(key)
+    }
+}
+
+/// Encrypts results under one passphrase
+///
+/// The key is derived once, with a fresh salt, so sealing each result only costs a
+/// cipher pass and never stalls the search.
+pub struct Sealer {
+    params: KdfParams,
+    cipher: XChaCha20Poly1305,
+}
+
+impl Sealer {
+    pub fn new(passphrase: &str) -> Result<Self> {
+        Self::with_cost(passphrase, DEFAULT_LOG_N)
+    }
+
+    /// Derive the key with a scrypt cost of 2^`log_n` rounds
+    pub fn with_cost(passphrase: &str, log_n: u8) -> Result<Self> {
+        if passphrase.is_empty() {
+            return Err(VanityError::Keystore("Passphrase must not be empty".to_string()));
+        }
+
+        let mut salt = [0u8; SALT_LEN];
+        rand::rngs::OsRng.fill_bytes(&mut salt);
+        let params = KdfParams {
+            log_n,
+            r: KDF_R,
+            p: KDF_P,
+            salt: hex::encode(salt),
+        };
+        let key = params.derive_key(passphrase)?;
+
+        Ok(Self {
+            params,
+            cipher: XChaCha20Poly1305::new(Key::from_slice(key.as_ref())),
+        })
+    }
+
+    /// Encrypt a result's key material into a keystore entry
+    pub fn seal(&self, result: &VanityResult) -> Result<KeystoreEntry> {
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::rngs::OsRng.fill_bytes(&mut nonce);
+
+        let plaintext = Zeroizing::new(serde_json::to_vec(&result.key)?);
+        let ciphertext = self
+            .cipher
+            .encrypt(XNonce::from_slice(&nonce), Payload { msg: &plaintext, aad: result.public_key.as_bytes() })
+            .map_err(|_| VanityError::Keystore("Encryption failed".to_string()))?;
+
+        Ok(KeystoreEntry {
+            public_key: result.public_key.clone(),
+            pattern: result.pattern.clone(),
+            attempts: result.attempts,
+            time_elapsed: result.time_elapsed,
+            crypto: Crypto {
+                kdf: "scrypt".to_string(),
+                kdfparams: self.params.clone(),
+                cipher: "xchacha20poly1305".to_string(),
+                nonce: hex::encode(nonce),
+                ciphertext: hex::encode(ciphertext),
+            },
+        })
+    }
+}
+
+/// Decrypt keystore entries back into results
+///
+/// Entries written by the same run share a salt, so each distinct set of KDF
+/// parameters is only derived once.
+pub fn open(entries: Vec<KeystoreEntry>, passphrase: &str) -> Result<Vec<VanityResult>> {
+    let mut keys: Vec<(KdfParams, XChaCha20Poly1305)> = Vec::new();
+    let mut results = Vec::with_capacity(entries.len());
+
+    for entry in entries {
+        let crypto = &entry.crypto;
+        if crypto.kdf != "scrypt" || crypto.cipher != "xchacha20poly1305" {
+            return Err(VanityError::Keystore(format!("Unsupported keystore encryption: {} / {}", crypto.kdf, crypto.cipher)));
+        }
+
+        let cipher = match keys.iter().position(|(params, _)| *params == crypto.kdfparams) {
+            Some(index) => &keys[index].1,
+            None => {
+                let key = crypto.kdfparams.derive_key(passphrase)?;
+                keys.push((crypto.kdfparams.clone(), XChaCha20Poly1305::new(Key::from_slice(key.as_ref()))));
+                &keys[keys.len() - 1].1
+            }
+        };
+
+        let nonce = decode_hex("nonce", &crypto.nonce)?;
+        if nonce.len() != NONCE_LEN {
+            return Err(VanityError::Keystore(format!("Invalid nonce for {}", entry.public_key)));
+        }
+        let ciphertext = decode_hex("ciphertext", &crypto.ciphertext)?;
+        let plaintext = Zeroizing::new(cipher
+            .decrypt(XNonce::from_slice(&nonce), Payload { msg: &ciphertext, aad: entry.public_key.as_bytes() })
+            .map_err(|_| VanityError::Keystore(format!("Wrong passphrase or corrupted entry for {}", entry.public_key)))?);
+
+        results.push(VanityResult {
+            public_key: entry.public_key,
+            key: serde_json::from_slice(&plaintext)?,
+            pattern: entry.pattern,
+            attempts: entry.attempts,
+            time_elapsed: entry.time_elapsed,
+        });
+    }
+
+    Ok(results)
+}
+
+/// Read every entry of a keystore file written by `ResultWriter::create_encrypted`
+pub fn read_keystore(path: &str) -> Result<Vec<KeystoreEntry>> {
+    let content = std::fs::read_to_string(path)?;
+    serde_json::from_str(&content).map_err(|e| VanityError::Keystore(format!("{} is not a keystore file: {}", path, e)))
+}
+
+/// Hex-decode one field of a keystore entry
+fn decode_hex(field: &str, value: &str) -> Result<Vec<u8>> {
+    hex::decode(value).map_err(|e| VanityError::Keystore(format!("Invalid {} in keystore: {}", field, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keygen::{KeyMaterial, SearchMode};
+
+    fn result() -> VanityResult {
+        let candidate = SearchMode::Keypair.generate(0);
+        VanityResult {
+            public_key: candidate.pubkey().to_string(),
+            key: candidate.into_key_material(),
+            pattern: "A".to_string(),
+            attempts: 42,
+            time_elapsed: Duration::from_millis(1500),
+        }
+    }
+
+    #[test]
+    fn test_seal_and_open_round_trip() {
+        let sealer = Sealer::with_cost("correct horse", 4).unwrap();
+        let results = [result(), result()];
+        let entries: Vec<KeystoreEntry> = results.iter().map(|r| sealer.seal(r).unwrap()).collect();
+
+        // No secret reaches the serialized keystore
+        let json = serde_json::to_string(&entries).unwrap();
+        let KeyMaterial::Keypair { private_key } = &results[0].key else {
+            panic!("expected keypair key material");
+        };
+        assert!(!json.contains(private_key.expose()));
+        assert!(!json.contains("private_key"));
+
+        let entries: Vec<KeystoreEntry> = serde_json::from_str(&json).unwrap();
+        let opened = open(entries.clone(), "correct horse").unwrap();
+        assert_eq!(opened.len(), 2);
+        for (original, opened) in results.iter().zip(&opened) {
+            assert_eq!(opened.public_key, original.public_key);
+            assert_eq!(opened.key.to_keypair().unwrap().to_bytes(), original.key.to_keypair().unwrap().to_bytes());
+        }
+
+        assert!(open(entries.clone(), "wrong horse").is_err());
+
+        // Moving a ciphertext onto another address fails authentication
+        let mut swapped = entries[0].clone();
+        swapped.crypto = entries[1].crypto.clone();
+        assert!(open(vec![swapped], "correct horse").is_err());
+    }
+
+    #[test]
+    fn test_empty_passphrase_rejected() {
+        assert!(Sealer::with_cost("", 4).is_err());
+    }
+}
@@ -0,0 +1,45 @@
+//! High-performance Solana vanity address generator
+//!
+//! Describe the search with [`VanityOptions::builder`], then run it with a
+//! [`VanityGenerator`]. Results can be collected when the run ends, handled the
+//! moment they are found through [`VanityGenerator::with_result_handler`], or
+//! received together with progress updates from [`VanityGenerator::generate_stream`].
+//! [`ResultWriter`] and [`output::write_keypair_file`] save results as they arrive,
+//! and [`keystore`] encrypts them under a passphrase.
+//!
+//! ```no_run
+//! use solana_vanity::{PatternType, VanityGenerator, VanityOptions};
+//!
+//! # fn main() -> solana_vanity::Result<()> {
+//! let options = VanityOptions::builder()
+//!     .pattern("RUST", 1)
+//!     .pattern_type(PatternType::StartsWith)
+//!     .build()?;
+//!
+//! let outcome = VanityGenerator::new().generate_multiple(options, num_cpus::get())?;
+//! for result in &outcome.results {
+//!     println!("{}", result.public_key);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod error;
+pub mod keygen;
+pub mod keystore;
+pub mod output;
+pub mod pattern;
+mod prefilter;
+pub mod progress;
+pub mod secret;
+pub mod vanity;
+
+pub use error::{Result, VanityError};
+pub use keygen::{KeyMaterial, SearchMode};
+pub use output::{ExistingFile, OutputFormat, ResultWriter};
+pub use progress::{IndicatifReporter, JsonLinesReporter, ProgressReporter, SilentReporter};
+pub use secret::SecretString;
+pub use vanity::{
+    CancelHandle, GenerationOutcome, PatternTarget, PatternType, ResultHandler, ResultStream, SearchEvent, SearchProgress,
+    StopReason, VanityGenerator, VanityOptions, VanityOptionsBuilder, VanityResult,
+};
@@ -1,23 +1,52 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use console::style;
 use std::time::{Duration, Instant};
 
-mod vanity;
-use vanity::{VanityGenerator, VanityOptions, VanityResult, PatternType};
+mod benchmark;
+mod passphrase;
+mod report;
+mod server;
+use solana_sdk::pubkey::Pubkey;
+use report::{output_csv, output_json, output_text, print_difficulty_estimate, print_summary, report_unsaved, warn, UnsavedKeys};
+use solana_vanity::output::{self, ExistingFile, OutputFormat, ResultWriter};
+use solana_vanity::{keygen, keystore, pattern, vanity};
+use solana_vanity::{
+    IndicatifReporter, JsonLinesReporter, PatternTarget, PatternType, ProgressReporter, SearchMode,
+    VanityError, VanityGenerator, VanityOptions, VanityResult,
+};
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 
 #[derive(Parser)]
 #[command(name = "solana-vanity")]
 #[command(about = "High-performance Solana vanity address generator")]
 #[command(version)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
-    /// Pattern to match (e.g., "ABC", "SOL", "XYZ")
-    #[arg(short, long)]
-    pattern: String,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Pattern to match (e.g., "ABC", "RUST", "XYZ"); repeat to search for several at once
+    #[arg(short, long, required_unless_present_any = ["pattern_file", "prefix", "suffix"])]
+    pattern: Vec<String>,
 
-    /// Type of pattern matching
+    /// File with one pattern per line, optionally followed by a count (e.g., "ABC 3")
+    #[arg(long)]
+    pattern_file: Option<String>,
+
+    /// Type of pattern matching (starts_with, ends_with, contains, regex, glob)
     #[arg(long, value_enum, default_value = "starts_with")]
     pattern_type: PatternType,
 
+    /// Prefix the address must start with (combine with --suffix)
+    #[arg(long, conflicts_with_all = ["pattern", "pattern_file", "pattern_type"])]
+    prefix: Option<String>,
+
+    /// Suffix the address must end with, in addition to any prefix or starts_with pattern
+    #[arg(long)]
+    suffix: Option<String>,
+
     /// Maximum number of attempts
     #[arg(long, default_value = "10000000")]
     max_attempts: u64,
@@ -30,59 +59,205 @@ struct Cli {
     #[arg(long, default_value = "0")]
     threads: usize,
 
+    /// Pin each worker thread to its own CPU core
+    #[arg(long)]
+    pin_cores: bool,
+
+    /// Search BIP39 seed phrases (m/44'/501'/0'/0') instead of raw keypairs; much slower
+    #[arg(long)]
+    mnemonic: bool,
+
+    /// Search create_with_seed addresses derived from this base pubkey (requires --owner)
+    #[arg(long, requires = "owner", conflicts_with_all = ["mnemonic", "keypair_dir"])]
+    seed_base: Option<Pubkey>,
+
+    /// Owner program of the create_with_seed addresses
+    #[arg(long, requires = "seed_base")]
+    owner: Option<Pubkey>,
+
+    /// Text prepended to the attempt number to form each seed
+    #[arg(long, requires = "seed_base", default_value = "")]
+    seed_prefix: String,
+
+    /// Search program-derived addresses of this program, adding a u64 nonce seed per attempt
+    #[arg(long, conflicts_with_all = ["mnemonic", "keypair_dir", "seed_base"])]
+    pda_program: Option<Pubkey>,
+
+    /// Fixed PDA seed, before the nonce; repeatable (text, "hex:<bytes>" or "pubkey:<base58>")
+    #[arg(long, requires = "pda_program")]
+    pda_seed: Vec<String>,
+
     /// Case sensitive matching
     #[arg(short, long)]
     case_sensitive: bool,
 
-    /// Generate multiple addresses
+    /// Addresses to generate per pattern; repeat to set a count for each --pattern in order
     #[arg(long, default_value = "1")]
-    count: usize,
+    count: Vec<usize>,
 
-    /// Output format (json, csv, text)
+    /// Output format (json, csv, text); --encrypt keystores are always JSON
     #[arg(long, value_enum, default_value = "text")]
     format: OutputFormat,
 
-    /// Output file (optional)
+    /// Output file (optional); each result is written as soon as it is found
     #[arg(long)]
     output: Option<String>,
+
+    /// Overwrite --output if it already exists
+    #[arg(long, requires = "output")]
+    force: bool,
+
+    /// Add results to an existing --output file of the same format instead of replacing it
+    #[arg(long, requires = "output", conflicts_with = "force")]
+    append: bool,
+
+    /// Directory to write each keypair to as a solana-keygen compatible <pubkey>.json file
+    #[arg(long)]
+    keypair_dir: Option<PathBuf>,
+
+    /// Print private keys and seed phrases even when they are also saved to a file
+    #[arg(long)]
+    show_secrets: bool,
+
+    /// Never print private keys or seed phrases; they only go to --output or --keypair-dir
+    #[arg(long, conflicts_with = "show_secrets")]
+    redact: bool,
+
+    /// Encrypt the key material in --output under a passphrase
+    ///
+    /// The keystore is always JSON; --format then only chooses how results are printed.
+    #[arg(long, requires = "output", conflicts_with = "keypair_dir")]
+    encrypt: bool,
+
+    /// Progress output: an interactive bar with summaries, or NDJSON events on stderr
+    #[arg(long, value_enum, default_value = "bar")]
+    progress: ProgressMode,
 }
 
-#[derive(clap::ValueEnum, Clone)]
-enum OutputFormat {
-    Text,
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum ProgressMode {
+    /// Progress bar, configuration and summary for people
+    Bar,
+    /// One JSON event per line on stderr (started, heartbeat, found, finished); stdout only has results
     Json,
-    Csv,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Recover the addresses in a keystore written with --encrypt
+    Decrypt {
+        /// Keystore file to read
+        keystore: String,
+
+        /// Only recover this address
+        #[arg(long)]
+        public_key: Option<String>,
+
+        /// Write each recovered keypair to this directory as <pubkey>.json instead of printing it
+        #[arg(long)]
+        keypair_dir: Option<PathBuf>,
+    },
+    /// Measure search speed on this machine for every search mode and cache it for time estimates
+    Benchmark {
+        /// Type of pattern matching to measure
+        #[arg(long, value_enum, default_value = "starts_with")]
+        pattern_type: PatternType,
+
+        /// Measure case-sensitive matching
+        #[arg(long)]
+        case_sensitive: bool,
+
+        /// Seconds to measure each search mode for
+        #[arg(long, default_value = "2")]
+        duration: u64,
+
+        /// Number of threads to estimate the total speed for (0 = auto)
+        #[arg(long, default_value = "0")]
+        threads: usize,
+    },
+    /// Run an HTTP API that queues search jobs and runs them one at a time
+    Serve {
+        /// Address to listen on; results include private keys, so keep it local or behind a proxy
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        listen: std::net::SocketAddr,
+
+        /// Number of threads each job searches with (0 = auto)
+        #[arg(long, default_value = "0")]
+        threads: usize,
+    },
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
-    // Validate pattern
-    match vanity::validate_base58_pattern(&cli.pattern) {
-        Ok(_) => {},
-        Err(invalid_chars) => {
-            eprintln!("{}", style("❌ Error: Pattern contains invalid Base58 characters").red().bold());
-            let invalid_chars_str: String = invalid_chars.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", ");
-            eprintln!("{}{}", style("Invalid characters found: ").red(), style(invalid_chars_str).yellow().bold());
-            eprintln!();
-            eprintln!("{}", style("Base58 encoding excludes these characters:").yellow());
-            eprintln!("  • {} (zero)", style("0").red());
-            eprintln!("  • {} (capital O)", style("O").red());
-            eprintln!("  • {} (capital I)", style("I").red());
-            eprintln!("  • {} (lowercase L)", style("l").red());
-            eprintln!();
-            eprintln!("{}", style("Valid Base58 characters: 123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz").green());
-            eprintln!();
-            eprintln!("{}", style("Example valid patterns:").cyan());
-            eprintln!("  • {}", style("ABC").green());
-            eprintln!("  • {}", style("RUST").green());
-            eprintln!("  • {}", style("BYTE").green());
-            eprintln!("  • {} {}", style("SOL").red().strikethrough(), style("(contains 'O')").red());
+    match &cli.command {
+        Some(Command::Decrypt { keystore, public_key, keypair_dir }) => {
+            return decrypt_keystore(keystore, public_key.as_deref(), keypair_dir.as_deref());
+        }
+        Some(Command::Benchmark { pattern_type, case_sensitive, duration, threads }) => {
+            return benchmark::run(pattern_type, *case_sensitive, Duration::from_secs(*duration), *threads);
+        }
+        Some(Command::Serve { listen, threads }) => {
+            let thread_count = if *threads == 0 { num_cpus::get() } else { *threads };
+            return server::serve(*listen, thread_count).await;
+        }
+        None => {}
+    }
+
+    let (targets, pattern_type, suffix) = match load_pattern_targets(&cli) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("{}", style(format!("❌ Error: {}", e)).red().bold());
             std::process::exit(1);
         }
+    };
+
+    let mode = match search_mode(&cli) {
+        Ok(mode) => mode,
+        Err(e) => {
+            eprintln!("{}", style(format!("❌ Error: {}", e)).red().bold());
+            std::process::exit(1);
+        }
+    };
+
+    if cli.redact && !output::secrets_persisted(&mode, cli.output.is_some(), cli.keypair_dir.is_some()) {
+        let message = match mode {
+            SearchMode::Mnemonic => "--redact needs --output in --mnemonic mode; keypair files do not hold the seed phrase",
+            _ => "--redact needs --output or --keypair-dir, or the keys would be lost",
+        };
+        eprintln!("{}", style(format!("❌ Error: {}", message)).red().bold());
+        std::process::exit(1);
     }
 
+    // Validate patterns
+    for target in &targets {
+        validate_pattern(&target.pattern, &pattern_type, cli.case_sensitive);
+    }
+    if let Some(suffix) = &suffix {
+        validate_pattern(suffix, &PatternType::EndsWith, cli.case_sensitive);
+    }
+
+    let mut builder = VanityOptions::builder()
+        .pattern_type(pattern_type)
+        .case_sensitive(cli.case_sensitive)
+        .max_attempts(cli.max_attempts)
+        .max_time(Duration::from_secs(cli.max_time))
+        .mode(mode);
+    for target in targets {
+        builder = builder.pattern(target.pattern, target.count);
+    }
+    if let Some(suffix) = suffix {
+        builder = builder.suffix(suffix);
+    }
+    let options = match builder.build() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}", style(format!("❌ Error: {}", e)).red().bold());
+            std::process::exit(1);
+        }
+    };
+
     // Set up thread count
     let thread_count = if cli.threads == 0 {
         num_cpus::get()
@@ -90,137 +265,356 @@ async fn main() -> anyhow::Result<()> {
         cli.threads
     };
 
-    println!("{}", style("🦀 Solana Vanity Address Generator").bold().cyan());
-    println!("{}", style("Built with Rust for maximum performance").dim());
-    println!();
-
-    // Display configuration
-    println!("{}", style("Configuration:").bold().yellow());
-    println!("  Pattern: {}", style(&cli.pattern).green());
-    println!("  Type: {}", style(format!("{:?}", cli.pattern_type)).green());
-    println!("  Case sensitive: {}", style(cli.case_sensitive).green());
-    println!("  Max attempts: {}", style(cli.max_attempts.to_string()).green());
-    println!("  Max time: {}", style(format!("{}s", cli.max_time)).green());
-    println!("  Threads: {}", style(thread_count.to_string()).green());
-    println!("  Count: {}", style(cli.count.to_string()).green());
-    println!();
+    // Banners and summaries are for people; --progress json only emits events on stderr
+    let human = cli.progress == ProgressMode::Bar;
+    if human {
+        print_configuration(&cli, &options, thread_count);
+    }
 
-    // Estimate difficulty
-    let options = VanityOptions {
-        pattern: cli.pattern.clone(),
-        pattern_type: cli.pattern_type.clone(),
-        case_sensitive: cli.case_sensitive,
-        max_attempts: cli.max_attempts,
-        max_time: Duration::from_secs(cli.max_time),
+    let requested_count: usize = options.patterns.iter().map(|target| target.count).sum();
+    let reporter: Arc<dyn ProgressReporter> = match cli.progress {
+        ProgressMode::Bar => Arc::new(IndicatifReporter::new()),
+        ProgressMode::Json => Arc::new(JsonLinesReporter::new(std::io::stderr())),
     };
+    let mut generator = VanityGenerator::new()
+        .with_core_pinning(cli.pin_cores)
+        .with_progress_reporter(reporter);
 
-    let generator = VanityGenerator::new();
-    let probability = generator.estimate_probability(&options);
-    let expected_attempts = generator.estimate_expected_attempts(&options);
-    let estimated_time = generator.estimate_expected_time(&options);
+    // Open the output file and keypair directory up front so every result can be persisted
+    // the moment it is found
+    let unsaved = Arc::new(Mutex::new(UnsavedKeys::default()));
+    let mut result_writer = None;
+    if cli.output.is_some() || cli.keypair_dir.is_some() {
+        let existing = if cli.append {
+            ExistingFile::Append
+        } else if cli.force {
+            ExistingFile::Overwrite
+        } else {
+            ExistingFile::Refuse
+        };
+        // The directory goes first, so a bad one never leaves an output file behind
+        let prepared = cli.keypair_dir.as_deref().map(output::prepare_keypair_dir).transpose();
+        let writer = prepared.map_err(anyhow::Error::from).and_then(|_| match &cli.output {
+            Some(output_file) if cli.encrypt => passphrase::read_passphrase(true).and_then(|passphrase| {
+                let sealer = keystore::Sealer::new(&passphrase)?;
+                Ok(Some(ResultWriter::create_encrypted(output_file, sealer, &options.mode, existing)?))
+            }),
+            Some(output_file) => Ok(Some(ResultWriter::create(output_file, cli.format, &options.mode, existing)?)),
+            None => Ok(None),
+        });
+        let writer = match writer {
+            Ok(writer) => writer.map(|writer| Arc::new(Mutex::new(writer))),
+            Err(e) => {
+                let e = match e.downcast_ref::<VanityError>() {
+                    Some(VanityError::OutputExists(_)) => format!("{}; use --force to overwrite it or --append to add to it", e),
+                    _ => e.to_string(),
+                };
+                eprintln!("{}", style(format!("❌ Error: {}", e)).red().bold());
+                std::process::exit(1);
+            }
+        };
+        result_writer = writer.clone();
+        let keypair_dir = cli.keypair_dir.clone();
+        let progress = cli.progress;
+        let handler_unsaved = Arc::clone(&unsaved);
 
-    println!("{}", style("Difficulty Estimate:").bold().yellow());
-    println!("  Probability: {}", style(format!("{:.6}%", probability * 100.0)).green());
-    println!("  Expected attempts: {}", style(expected_attempts.to_string()).green());
-    println!("  Estimated time: {}", style(generator.format_duration(estimated_time)).green());
-    println!();
+        generator = generator.with_result_handler(Arc::new(move |result| {
+            if let Some(writer) = &writer {
+                if let Err(e) = writer.lock().unwrap().write(result) {
+                    warn(progress, format!("Failed to save {}: {}", result.public_key, e));
+                    handler_unsaved.lock().unwrap().output.push(result.public_key.clone());
+                }
+            }
+            if let Some(keypair_dir) = &keypair_dir {
+                if let Err(e) = output::write_keypair_file(keypair_dir, result) {
+                    warn(progress, format!("Failed to write keypair file for {}: {}", result.public_key, e));
+                    handler_unsaved.lock().unwrap().keypair_dir.push(result.public_key.clone());
+                }
+            }
+        }));
+    }
+    if human {
+        print_difficulty_estimate(&generator, &options, thread_count)?;
+    }
+
+    // On Ctrl-C, let workers drain so the results found so far are still printed and saved;
+    // a second Ctrl-C exits immediately
+    let interrupt_generator = generator.clone();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            if human {
+                eprintln!("{}", style("\n⏹  Interrupted, finishing up... (press Ctrl-C again to quit immediately)").yellow());
+            }
+            interrupt_generator.cancel();
+
+            if tokio::signal::ctrl_c().await.is_ok() {
+                std::process::exit(130);
+            }
+        }
+    });
 
     // Start generation
+    let mode = options.mode.clone();
     let start_time = Instant::now();
-    let (results, total_attempts) = generator.generate_multiple_parallel(
-        cli.count,
+    let outcome = generator.generate_multiple_parallel(
         options,
         thread_count,
     ).await?;
 
     let total_time = start_time.elapsed();
 
+    // A run that found nothing leaves no file behind to block the next one
+    if let Some(writer) = &result_writer {
+        if let Err(e) = writer.lock().unwrap().remove_if_empty() {
+            warn(cli.progress, format!("Failed to remove the empty output file: {}", e));
+        }
+    }
+
     // Display results
-    if results.is_empty() {
-        println!("{}", style("❌ No addresses found within the specified limits").red());
-        return Ok(());
+    if human {
+        if outcome.results.is_empty() {
+            println!("{}", style(format!("❌ No addresses found within the specified limits ({})", outcome.stop_reason)).red());
+            println!("  Total attempts: {}", style(outcome.total_attempts.to_string()).yellow());
+            return Ok(());
+        }
+        print_summary(&outcome, requested_count, total_time);
     }
+    let results = outcome.results;
 
-    println!("{}", style("✅ Generation Complete!").bold().green());
-    println!("  Total time: {}", style(format!("{:.2}s", total_time.as_secs_f64())).green());
-    println!("  Total attempts: {}", style(total_attempts.to_string()).green());
-    println!("  Average speed: {}", style(format!("{:.0} attempts/sec", 
-        total_attempts as f64 / total_time.as_secs_f64())).green());
-    println!();
+    // Keep secrets out of scrollback and logs once they are safely on disk; a result that
+    // failed to save is always printed in full, as stdout is the only copy of its key
+    let saved = output::secrets_persisted(&mode, cli.output.is_some(), cli.keypair_dir.is_some());
+    let redact = mode.has_secrets()
+        && (cli.redact || (saved && !cli.show_secrets && std::io::stdout().is_terminal()));
+    let unsaved = unsaved.lock().unwrap();
+    let redact_result = |result: &VanityResult| redact && !unsaved.contains(&result.public_key);
 
     // Output results
     match cli.format {
-        OutputFormat::Text => output_text(&results),
-        OutputFormat::Json => output_json(&results)?,
-        OutputFormat::Csv => output_csv(&results)?,
+        OutputFormat::Text => output_text(&results, redact_result),
+        OutputFormat::Json => output_json(&results, redact_result)?,
+        OutputFormat::Csv => output_csv(&results, &mode, redact_result)?,
+    }
+    if human && results.iter().any(redact_result) {
+        println!("{}", style("🔒 Secrets hidden; they are only in the saved files (use --show-secrets to print them)").dim());
     }
 
-    // Save to file if specified
-    if let Some(output_file) = cli.output {
-        save_results(&results, &output_file, &cli.format)?;
-        println!("{}", style(format!("Results saved to: {}", output_file)).green());
+    // Results were already saved as they were found; any that were not fail the run
+    if let Some(output_file) = &cli.output {
+        if !unsaved.output.is_empty() {
+            report_unsaved(cli.progress, output_file, &unsaved.output);
+        } else if human && cli.encrypt {
+            println!("{}", style(format!("Encrypted keystore saved to: {}", output_file)).green());
+        } else if human {
+            println!("{}", style(format!("Results saved to: {}", output_file)).green());
+        }
+    }
+    if let Some(keypair_dir) = &cli.keypair_dir {
+        if !unsaved.keypair_dir.is_empty() {
+            report_unsaved(cli.progress, &keypair_dir.display().to_string(), &unsaved.keypair_dir);
+        } else if human {
+            println!("{}", style(format!("Keypair files saved to: {}", keypair_dir.display())).green());
+        }
+    }
+    if !unsaved.output.is_empty() || !unsaved.keypair_dir.is_empty() {
+        std::process::exit(1);
     }
 
     Ok(())
 }
 
-fn output_text(results: &[VanityResult]) {
-    for (i, result) in results.iter().enumerate() {
-        println!("{}", style(format!("Address #{}", i + 1)).bold().cyan());
-        println!("  Public Key:  {}", style(&result.public_key).green());
-        println!("  Private Key: {}", style(&result.private_key).red());
-        println!("  Time:        {}", style(format!("{:.2}s", result.time_elapsed.as_secs_f64())).yellow());
-        println!();
+/// Print the banner and the search settings
+fn print_configuration(cli: &Cli, options: &VanityOptions, thread_count: usize) {
+    println!("{}", style("🦀 Solana Vanity Address Generator").bold().cyan());
+    println!("{}", style("Built with Rust for maximum performance").dim());
+    println!();
+
+    println!("{}", style("Configuration:").bold().yellow());
+    let pattern_list: Vec<String> = options
+        .patterns
+        .iter()
+        .map(|target| format!("{} (x{})", target.pattern, target.count))
+        .collect();
+    println!("  Patterns: {}", style(pattern_list.join(", ")).green());
+    if let Some(suffix) = &options.suffix {
+        println!("  Suffix: {}", style(suffix).green());
     }
+    println!("  Type: {}", style(format!("{:?}", options.pattern_type)).green());
+    println!("  Case sensitive: {}", style(options.case_sensitive).green());
+    match &options.mode {
+        SearchMode::Keypair => {}
+        SearchMode::Mnemonic => {
+            println!("  Mode: {}", style(format!("BIP39 mnemonic ({})", keygen::MNEMONIC_DERIVATION_PATH)).green());
+        }
+        SearchMode::Seed { base, owner, .. } => {
+            println!("  Mode: {}", style(format!("create_with_seed (base {}, owner {})", base, owner)).green());
+        }
+        SearchMode::Pda { program_id, seeds } => {
+            println!("  Mode: {}", style(format!("PDA (program {}, {} fixed seeds + u64 nonce)", program_id, seeds.len())).green());
+        }
+    }
+    println!("  Max attempts: {}", style(cli.max_attempts.to_string()).green());
+    println!("  Max time: {}", style(format!("{}s", cli.max_time)).green());
+    println!("  Threads: {}", style(thread_count.to_string()).green());
+    println!();
 }
 
-fn output_json(results: &[VanityResult]) -> anyhow::Result<()> {
-    let json = serde_json::to_string_pretty(results)?;
-    println!("{}", json);
+/// Decrypt a keystore and print its addresses, or write them out as keypair files
+fn decrypt_keystore(path: &str, public_key: Option<&str>, keypair_dir: Option<&std::path::Path>) -> anyhow::Result<()> {
+    let mut entries = keystore::read_keystore(path)?;
+    if let Some(public_key) = public_key {
+        entries.retain(|entry| entry.public_key == public_key);
+        if entries.is_empty() {
+            anyhow::bail!("{} is not in {}", public_key, path);
+        }
+    }
+
+    let results = match passphrase::read_passphrase(false).and_then(|passphrase| Ok(keystore::open(entries, &passphrase)?)) {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("{}", style(format!("❌ Error: {}", e)).red().bold());
+            std::process::exit(1);
+        }
+    };
+
+    match keypair_dir {
+        Some(keypair_dir) => {
+            for result in &results {
+                let path = output::write_keypair_file(keypair_dir, result)?;
+                println!("{}", style(format!("Keypair saved to: {}", path.display())).green());
+            }
+        }
+        None => output_text(&results, |_| false),
+    }
+
     Ok(())
 }
 
-fn output_csv(results: &[VanityResult]) -> anyhow::Result<()> {
-    println!("public_key,private_key,attempts,time_seconds");
-    for result in results {
-        println!("{},{},{},{}", 
-            result.public_key, 
-            result.private_key, 
-            result.attempts, 
-            result.time_elapsed.as_secs_f64()
-        );
+/// How candidate addresses should be generated, from the mode flags
+fn search_mode(cli: &Cli) -> anyhow::Result<SearchMode> {
+    Ok(if let (Some(base), Some(owner)) = (cli.seed_base, cli.owner) {
+        SearchMode::Seed {
+            base,
+            owner,
+            seed_prefix: cli.seed_prefix.clone(),
+        }
+    } else if let Some(program_id) = cli.pda_program {
+        SearchMode::Pda {
+            program_id,
+            seeds: cli.pda_seed.iter().map(|seed| keygen::parse_pda_seed(seed)).collect::<solana_vanity::Result<_>>()?,
+        }
+    } else if cli.mnemonic {
+        SearchMode::Mnemonic
+    } else {
+        SearchMode::Keypair
+    })
+}
+
+/// Validate a single pattern, printing a helpful error and exiting if it is invalid
+fn validate_pattern(pattern: &str, pattern_type: &PatternType, case_sensitive: bool) {
+    if let PatternType::Regex = pattern_type {
+        if let Err(message) = pattern::validate_regex_pattern(pattern, case_sensitive) {
+            eprintln!("{}", style("❌ Error: Invalid regex pattern").red().bold());
+            eprintln!("{}", style(message).yellow());
+            eprintln!();
+            eprintln!("{}", style("Valid Base58 characters: 123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz").green());
+            eprintln!();
+            eprintln!("{}", style("Example valid regexes:").cyan());
+            eprintln!("  • {}", style("^(SoL|Dex)[1-9]{2}").green());
+            eprintln!("  • {}", style("^BYTE|RUST$").green());
+            std::process::exit(1);
+        }
+    } else if let PatternType::Glob = pattern_type {
+        if let Err(message) = pattern::validate_glob_pattern(pattern, case_sensitive) {
+            eprintln!("{}", style("❌ Error: Invalid glob pattern").red().bold());
+            eprintln!("{}", style(message).yellow());
+            eprintln!();
+            eprintln!("{}", style("Valid Base58 characters: 123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz").green());
+            eprintln!();
+            eprintln!("{}", style("Example valid globs:").cyan());
+            eprintln!("  • {} {}", style("AB?D").green(), style("(? is any character)").dim());
+            eprintln!("  • {} {}", style("[ABC]x*9").green(), style("([...] is a class, * any run)").dim());
+            std::process::exit(1);
+        }
+    } else if let Err(invalid_chars) = vanity::validate_base58_pattern(pattern) {
+        eprintln!("{}", style("❌ Error: Pattern contains invalid Base58 characters").red().bold());
+        let invalid_chars_str: String = invalid_chars.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", ");
+        eprintln!("{}{}", style("Invalid characters found: ").red(), style(invalid_chars_str).yellow().bold());
+        eprintln!();
+        eprintln!("{}", style("Base58 encoding excludes these characters:").yellow());
+        eprintln!("  • {} (zero)", style("0").red());
+        eprintln!("  • {} (capital O)", style("O").red());
+        eprintln!("  • {} (capital I)", style("I").red());
+        eprintln!("  • {} (lowercase L)", style("l").red());
+        eprintln!();
+        eprintln!("{}", style("Valid Base58 characters: 123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz").green());
+        eprintln!();
+        eprintln!("{}", style("Example valid patterns:").cyan());
+        eprintln!("  • {}", style("ABC").green());
+        eprintln!("  • {}", style("RUST").green());
+        eprintln!("  • {}", style("BYTE").green());
+        eprintln!("  • {} {}", style("SOL").red().strikethrough(), style("(contains 'O')").red());
+        std::process::exit(1);
     }
-    Ok(())
 }
 
-fn save_results(results: &[VanityResult], filename: &str, format: &OutputFormat) -> anyhow::Result<()> {
-    let content = match format {
-        OutputFormat::Text => {
-            let mut text = String::new();
-            for (i, result) in results.iter().enumerate() {
-                text.push_str(&format!("Address #{}\n", i + 1));
-                text.push_str(&format!("Public Key:  {}\n", result.public_key));
-                text.push_str(&format!("Private Key: {}\n", result.private_key));
-                text.push_str(&format!("Attempts:    {}\n", result.attempts));
-                text.push_str(&format!("Time:        {:.2}s\n\n", result.time_elapsed.as_secs_f64()));
-            }
-            text
-        },
-        OutputFormat::Json => serde_json::to_string_pretty(results)?,
-        OutputFormat::Csv => {
-            let mut csv = String::from("public_key,private_key,attempts,time_seconds\n");
-            for result in results {
-                csv.push_str(&format!("{},{},{},{}\n", 
-                    result.public_key, 
-                    result.private_key, 
-                    result.attempts, 
-                    result.time_elapsed.as_secs_f64()
-                ));
+/// Collect patterns from `--pattern`/`--prefix` flags and `--pattern-file`, pairing each with its count
+///
+/// Also resolves the pattern type and required suffix implied by `--prefix`/`--suffix`.
+fn load_pattern_targets(cli: &Cli) -> anyhow::Result<(Vec<PatternTarget>, PatternType, Option<String>)> {
+    let default_count = if cli.count.len() == 1 { cli.count[0] } else { 1 };
+    let patterns: Vec<&String> = cli.pattern.iter().chain(cli.prefix.iter()).collect();
+
+    let mut targets: Vec<PatternTarget> = if cli.count.len() == 1 {
+        patterns.iter().map(|p| PatternTarget::new(p, default_count)).collect()
+    } else if cli.count.len() == patterns.len() {
+        patterns.iter().zip(&cli.count).map(|(p, &count)| PatternTarget::new(p, count)).collect()
+    } else {
+        anyhow::bail!(
+            "Expected 1 or {} --count values, got {}",
+            patterns.len(),
+            cli.count.len()
+        );
+    };
+
+    if let Some(pattern_file) = &cli.pattern_file {
+        let content = std::fs::read_to_string(pattern_file)?;
+        for (line_number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
             }
-            csv
-        },
+
+            let mut fields = line.split_whitespace();
+            let pattern = fields.next().unwrap_or_default();
+            let count = match fields.next() {
+                Some(count) => count.parse().map_err(|_| {
+                    anyhow::anyhow!("{}:{}: invalid count '{}'", pattern_file, line_number + 1, count)
+                })?,
+                None => default_count,
+            };
+            targets.push(PatternTarget::new(pattern, count));
+        }
+    }
+
+    let mut pattern_type = if cli.prefix.is_some() {
+        PatternType::StartsWith
+    } else {
+        cli.pattern_type.clone()
     };
+    let mut suffix = cli.suffix.clone();
 
-    std::fs::write(filename, content)?;
-    Ok(())
-}
\ No newline at end of file
+    // A lone --suffix is just an ends_with search
+    if targets.is_empty() {
+        if let Some(suffix) = suffix.take() {
+            targets.push(PatternTarget::new(&suffix, default_count));
+            pattern_type = PatternType::EndsWith;
+        }
+    }
+
+    if targets.is_empty() {
+        anyhow::bail!("No patterns given");
+    }
+
+    Ok((targets, pattern_type, suffix))
+}
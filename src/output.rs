@@ -0,0 +1,587 @@
+use serde::Serialize;
+use solana_sdk::signature::Signer;
+use std::borrow::Cow;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use zeroize::Zeroizing;
+
+use crate::error::{Result, VanityError};
+use crate::keygen::SearchMode;
+use crate::keystore::Sealer;
+use crate::vanity::VanityResult;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" | "txt" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err(format!("Invalid output format: {}", s)),
+        }
+    }
+}
+
+/// Whether the chosen destinations keep every secret of results found in `mode`
+///
+/// Keypair files hold only the derived keypair, so a seed phrase is kept by the output file alone.
+pub fn secrets_persisted(mode: &SearchMode, output: bool, keypair_dir: bool) -> bool {
+    output || (keypair_dir && matches!(mode, SearchMode::Keypair))
+}
+
+/// Quote a CSV field per RFC 4180 when it holds a comma, quote or line break
+fn csv_field(value: Cow<str>) -> Cow<str> {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\"")).into()
+    } else {
+        value
+    }
+}
+
+/// Header row for CSV output of results found in `mode`
+///
+/// With `redact`, the key material columns are left out.
+pub fn csv_header(mode: &SearchMode, redact: bool) -> String {
+    if redact {
+        "public_key,attempts,time_seconds,pattern".to_string()
+    } else {
+        format!("public_key,{},attempts,time_seconds,pattern", mode.key_columns().join(","))
+    }
+}
+
+/// Format a single result as a CSV row (without trailing newline), matching `csv_header`
+pub fn csv_row(result: &VanityResult, redact: bool) -> String {
+    let mut values: Vec<Cow<str>> = vec![result.public_key.as_str().into()];
+    if !redact {
+        values.extend(result.key.fields().into_iter().map(|(_, value)| value));
+    }
+    values.push(result.attempts.to_string().into());
+    values.push(result.time_elapsed.as_secs_f64().to_string().into());
+    values.push(result.pattern.as_str().into());
+    values.into_iter().map(csv_field).collect::<Vec<_>>().join(",")
+}
+
+/// The public parts of a result, serialized in the same field order but without key material
+#[derive(Debug, Serialize)]
+pub struct RedactedResult<'a> {
+    pub public_key: &'a str,
+    pub pattern: &'a str,
+    pub attempts: u64,
+    pub time_elapsed: Duration,
+}
+
+impl<'a> From<&'a VanityResult> for RedactedResult<'a> {
+    fn from(result: &'a VanityResult) -> Self {
+        Self {
+            public_key: &result.public_key,
+            pattern: &result.pattern,
+            attempts: result.attempts,
+            time_elapsed: result.time_elapsed,
+        }
+    }
+}
+
+/// Format a single result as a plain text block
+pub fn text_entry(number: usize, result: &VanityResult) -> String {
+    let mut text = String::new();
+    text.push_str(&format!("Address #{}\n", number));
+    text.push_str(&format!("Public Key:  {}\n", result.public_key));
+    for (label, value) in result.key.fields() {
+        text.push_str(&format!("{:<13}{}\n", format!("{}:", label), value));
+    }
+    text.push_str(&format!("Pattern:     {}\n", result.pattern));
+    text.push_str(&format!("Attempts:    {}\n", result.attempts));
+    text.push_str(&format!("Time:        {:.2}s\n\n", result.time_elapsed.as_secs_f64()));
+    text
+}
+
+/// What to do when the output file already exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExistingFile {
+    /// Fail rather than risk destroying previously generated keys
+    Refuse,
+    Overwrite,
+    /// Add new results to the ones already in the file
+    Append,
+}
+
+/// Open the output file readable and writable by its owner only
+fn open_output(path: &str, existing: ExistingFile) -> Result<File> {
+    let mut options = OpenOptions::new();
+    options.read(true).write(true);
+    match existing {
+        ExistingFile::Refuse => options.create_new(true),
+        ExistingFile::Overwrite => options.create(true).truncate(true),
+        ExistingFile::Append => options.create(true),
+    };
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let file = options.open(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::AlreadyExists => VanityError::OutputExists(path.to_string()),
+        _ => e.into(),
+    })?;
+
+    // The mode only applies to new files, so tighten an existing one as well
+    #[cfg(unix)]
+    std::fs::set_permissions(path, std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+
+    Ok(file)
+}
+
+/// Writes results to a file one at a time, as soon as they are found
+///
+/// Every write is flushed to disk before returning, so a crash or interrupt never
+/// loses a key that was already reported. JSON output stays a valid array after
+/// each write: the closing bracket is rewritten behind every new entry.
+pub struct ResultWriter {
+    file: File,
+    format: OutputFormat,
+    written: usize,
+    /// Where the JSON closing bracket starts, i.e. where the next entry goes
+    json_tail: u64,
+    /// Encrypts each entry's key material when writing a keystore
+    sealer: Option<Sealer>,
+    path: PathBuf,
+    /// Whether the file held nothing before this writer, so it can go again if no result is written
+    fresh: bool,
+}
+
+impl ResultWriter {
+    /// Create the output file with owner-only permissions and write any header for results found in `mode`
+    ///
+    /// With `ExistingFile::Append`, an existing file must hold results in the same
+    /// format (and, for CSV, the same columns); new results are added after them.
+    pub fn create(path: &str, format: OutputFormat, mode: &SearchMode, existing: ExistingFile) -> Result<Self> {
+        Self::open(path, format, mode, existing, None)
+    }
+
+    /// Create a JSON keystore whose entries hold key material encrypted by `sealer`
+    pub fn create_encrypted(path: &str, sealer: Sealer, mode: &SearchMode, existing: ExistingFile) -> Result<Self> {
+        Self::open(path, OutputFormat::Json, mode, existing, Some(sealer))
+    }
+
+    fn open(path: &str, format: OutputFormat, mode: &SearchMode, existing: ExistingFile, sealer: Option<Sealer>) -> Result<Self> {
+        let mut file = open_output(path, existing)?;
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
+        let content = Zeroizing::new(content);
+
+        let mut writer = Self {
+            file,
+            format,
+            written: 0,
+            json_tail: 0,
+            sealer,
+            path: PathBuf::from(path),
+            fresh: content.is_empty(),
+        };
+
+        if writer.fresh {
+            match writer.format {
+                OutputFormat::Text => {}
+                OutputFormat::Json => {
+                    writer.file.write_all(b"[")?;
+                    writer.json_tail = 1;
+                    writer.file.write_all(b"\n]\n")?;
+                }
+                OutputFormat::Csv => writeln!(writer.file, "{}", csv_header(mode, false))?,
+            }
+        } else {
+            writer.resume(path, &content, mode)?;
+        }
+        writer.file.sync_all()?;
+
+        Ok(writer)
+    }
+
+    /// Pick up after the results already in a file being appended to
+    fn resume(&mut self, path: &str, content: &str, mode: &SearchMode) -> Result<()> {
+        match self.format {
+            OutputFormat::Text => {
+                self.written = content.lines().filter(|line| line.starts_with("Address #")).count();
+            }
+            OutputFormat::Json => {
+                let entries: Vec<serde_json::Value> = serde_json::from_str(content)
+                    .map_err(|e| VanityError::Output(format!("Cannot append to {}: not a JSON result list ({})", path, e)))?;
+                let encrypted = self.sealer.is_some();
+                if entries.iter().any(|entry| entry.get("crypto").is_some() != encrypted) {
+                    let kind = if encrypted { "plaintext results" } else { "an encrypted keystore" };
+                    return Err(VanityError::Output(format!("Cannot append to {}: it holds {}", path, kind)));
+                }
+
+                // The next entry goes right after the last one, replacing the closing bracket
+                let close = content.rfind(']').expect("a parsed JSON array ends with ']'");
+                self.json_tail = content[..close].trim_end().len() as u64;
+                self.written = entries.len();
+            }
+            OutputFormat::Csv => {
+                let header = csv_header(mode, false);
+                if content.lines().next() != Some(header.as_str()) {
+                    return Err(VanityError::Output(format!("Cannot append to {}: its columns differ from '{}'", path, header)));
+                }
+                if !content.ends_with('\n') {
+                    self.file.write_all(b"\n")?;
+                }
+                self.written = content.lines().count() - 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Delete the file if this writer created it and no result was ever written to it
+    ///
+    /// Returns whether the file was removed. Later writes fail once it is gone.
+    pub fn remove_if_empty(&mut self) -> Result<bool> {
+        if !self.fresh || self.written > 0 {
+            return Ok(false);
+        }
+        std::fs::remove_file(&self.path)?;
+        self.fresh = false;
+        Ok(true)
+    }
+
+    /// Append one result and flush it to disk
+    pub fn write(&mut self, result: &VanityResult) -> Result<()> {
+        match self.format {
+            OutputFormat::Text => self.file.write_all(Zeroizing::new(text_entry(self.written + 1, result)).as_bytes())?,
+            OutputFormat::Json => {
+                // Indent to match `serde_json::to_string_pretty` of the whole array
+                let entry = Zeroizing::new(match &self.sealer {
+                    Some(sealer) => serde_json::to_string_pretty(&sealer.seal(result)?)?,
+                    None => serde_json::to_string_pretty(result)?,
+                });
+                let mut indented = Zeroizing::new(String::with_capacity(entry.len() * 2));
+                for (i, line) in entry.lines().enumerate() {
+                    if i > 0 {
+                        indented.push('\n');
+                    }
+                    indented.push_str("  ");
+                    indented.push_str(line);
+                }
+                let separator = if self.written == 0 { "\n" } else { ",\n" };
+
+                self.file.seek(SeekFrom::Start(self.json_tail))?;
+                self.file.write_all(separator.as_bytes())?;
+                self.file.write_all(indented.as_bytes())?;
+                self.json_tail = self.file.stream_position()?;
+                self.file.write_all(b"\n]\n")?;
+                // Drop anything an appended-to file had after its closing bracket
+                let end = self.file.stream_position()?;
+                self.file.set_len(end)?;
+            }
+            OutputFormat::Csv => writeln!(self.file, "{}", *Zeroizing::new(csv_row(result, false)))?,
+        }
+
+        self.file.sync_data()?;
+        self.written += 1;
+        Ok(())
+    }
+}
+
+/// Create the directory keypair files go to and make sure files can be written there
+///
+/// Call it before the search starts, so a bad directory fails the run up front
+/// instead of after the first match.
+pub fn prepare_keypair_dir(dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(dir).map_err(|e| VanityError::Output(format!("Cannot create {}: {}", dir.display(), e)))?;
+
+    let probe = dir.join(format!(".solana-vanity-{}.tmp", std::process::id()));
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+        .map_err(|e| VanityError::Output(format!("Cannot write keypair files to {}: {}", dir.display(), e)))?;
+    std::fs::remove_file(&probe)?;
+
+    Ok(())
+}
+
+/// Write a result as a `solana-keygen` compatible `<pubkey>.json` keypair file in `dir`
+///
+/// The file holds the 64-byte keypair as a JSON array, which the Solana CLI and
+/// `solana-keygen` read directly. It is created with owner-only permissions.
+pub fn write_keypair_file(dir: &Path, result: &VanityResult) -> Result<PathBuf> {
+    let keypair = result.key.to_keypair()?;
+    if keypair.pubkey().to_string() != result.public_key {
+        return Err(VanityError::InvalidKeyMaterial(format!("Private key does not match {}", result.public_key)));
+    }
+
+    let path = dir.join(format!("{}.json", result.public_key));
+    solana_sdk::signature::write_keypair_file(&keypair, &path).map_err(|e| VanityError::Output(e.to_string()))?;
+    File::open(&path)?.sync_all()?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keygen::KeyMaterial;
+    use solana_sdk::signature::Keypair;
+
+    fn result(public_key: &str) -> VanityResult {
+        VanityResult {
+            public_key: public_key.to_string(),
+            key: KeyMaterial::Keypair {
+                private_key: "secret".to_string().into(),
+            },
+            pattern: "A".to_string(),
+            attempts: 42,
+            time_elapsed: Duration::from_millis(1500),
+        }
+    }
+
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("solana-vanity-{}-{}", std::process::id(), name))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn test_json_stays_valid_after_every_write() {
+        let path = temp_path("incremental.json");
+        let mut writer = ResultWriter::create(&path, OutputFormat::Json, &SearchMode::Keypair, ExistingFile::Refuse).unwrap();
+
+        let parsed: Vec<VanityResult> = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(parsed.is_empty());
+
+        let results = vec![result("A1"), result("A2"), result("A3")];
+        for (i, r) in results.iter().enumerate() {
+            writer.write(r).unwrap();
+            let content = std::fs::read_to_string(&path).unwrap();
+            let parsed: Vec<VanityResult> = serde_json::from_str(&content).unwrap();
+            assert_eq!(parsed.len(), i + 1);
+            assert_eq!(parsed[i].public_key, r.public_key);
+        }
+
+        // Byte-for-byte what writing the whole array at once would produce
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content, format!("{}\n", serde_json::to_string_pretty(&results).unwrap()));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_existing_files_are_protected() {
+        let path = temp_path("protected.json");
+        let mut writer = ResultWriter::create(&path, OutputFormat::Json, &SearchMode::Keypair, ExistingFile::Refuse).unwrap();
+        writer.write(&result("A1")).unwrap();
+        drop(writer);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        // The earlier key survives a second run that is not allowed to overwrite
+        assert!(ResultWriter::create(&path, OutputFormat::Json, &SearchMode::Keypair, ExistingFile::Refuse).is_err());
+        let parsed: Vec<VanityResult> = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(parsed.len(), 1);
+
+        ResultWriter::create(&path, OutputFormat::Json, &SearchMode::Keypair, ExistingFile::Overwrite).unwrap();
+        let parsed: Vec<VanityResult> = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(parsed.is_empty());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_append_merges_into_existing_results() {
+        let path = temp_path("append.json");
+        let mut writer = ResultWriter::create(&path, OutputFormat::Json, &SearchMode::Keypair, ExistingFile::Append).unwrap();
+        writer.write(&result("A1")).unwrap();
+        drop(writer);
+        let mut writer = ResultWriter::create(&path, OutputFormat::Json, &SearchMode::Keypair, ExistingFile::Append).unwrap();
+        writer.write(&result("A2")).unwrap();
+        writer.write(&result("A3")).unwrap();
+
+        let expected = vec![result("A1"), result("A2"), result("A3")];
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content, format!("{}\n", serde_json::to_string_pretty(&expected).unwrap()));
+
+        // Plaintext results and keystore entries never end up in one file
+        let sealer = Sealer::with_cost("passphrase", 4).unwrap();
+        assert!(ResultWriter::create_encrypted(&path, sealer, &SearchMode::Keypair, ExistingFile::Append).is_err());
+        std::fs::remove_file(&path).unwrap();
+
+        let path = temp_path("append.csv");
+        let mut writer = ResultWriter::create(&path, OutputFormat::Csv, &SearchMode::Keypair, ExistingFile::Append).unwrap();
+        writer.write(&result("A1")).unwrap();
+        drop(writer);
+        let mut writer = ResultWriter::create(&path, OutputFormat::Csv, &SearchMode::Keypair, ExistingFile::Append).unwrap();
+        writer.write(&result("A2")).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content.lines().collect::<Vec<_>>(), vec!["public_key,private_key,attempts,time_seconds,pattern", "A1,secret,42,1.5,A", "A2,secret,42,1.5,A"]);
+
+        // Results from another search mode have different columns
+        assert!(ResultWriter::create(&path, OutputFormat::Csv, &SearchMode::Mnemonic, ExistingFile::Append).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_encrypted_writer_produces_keystore() {
+        let path = temp_path("keystore.json");
+        let sealer = Sealer::with_cost("passphrase", 4).unwrap();
+        let mut writer = ResultWriter::create_encrypted(&path, sealer, &SearchMode::Keypair, ExistingFile::Refuse).unwrap();
+        writer.write(&result("A1")).unwrap();
+        writer.write(&result("A2")).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("secret"));
+        let entries = crate::keystore::read_keystore(&path).unwrap();
+        let opened = crate::keystore::open(entries, "passphrase").unwrap();
+        assert_eq!(opened.iter().map(|r| r.public_key.as_str()).collect::<Vec<_>>(), vec!["A1", "A2"]);
+        assert!(matches!(&opened[0].key, KeyMaterial::Keypair { private_key } if private_key.expose() == "secret"));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_keypair_file_round_trip() {
+        let keypair = Keypair::new();
+        let result = VanityResult {
+            public_key: keypair.pubkey().to_string(),
+            key: KeyMaterial::Keypair {
+                private_key: bs58::encode(keypair.to_bytes()).into_string().into(),
+            },
+            ..result("unused")
+        };
+
+        let dir = std::path::PathBuf::from(temp_path("keypairs"));
+        let path = write_keypair_file(&dir, &result).unwrap();
+        assert_eq!(path, dir.join(format!("{}.json", result.public_key)));
+
+        let loaded = solana_sdk::signature::read_keypair_file(&path).unwrap();
+        assert_eq!(loaded.pubkey(), keypair.pubkey());
+        assert_eq!(loaded.to_bytes(), keypair.to_bytes());
+
+        // Mismatched key material is rejected rather than written under the wrong name
+        let mismatched = VanityResult {
+            public_key: Keypair::new().pubkey().to_string(),
+            ..result
+        };
+        assert!(write_keypair_file(&dir, &mismatched).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_prepare_keypair_dir() {
+        let dir = std::path::PathBuf::from(temp_path("prepared")).join("nested");
+        prepare_keypair_dir(&dir).unwrap();
+        assert!(dir.is_dir());
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+
+        // A path below a regular file can never hold keypair files
+        let file = temp_path("not-a-dir");
+        std::fs::write(&file, "").unwrap();
+        assert!(prepare_keypair_dir(&Path::new(&file).join("keys")).is_err());
+
+        std::fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+        std::fs::remove_file(&file).unwrap();
+    }
+
+    #[test]
+    fn test_empty_output_is_removed() {
+        let path = temp_path("empty.json");
+        let mut writer = ResultWriter::create(&path, OutputFormat::Json, &SearchMode::Keypair, ExistingFile::Refuse).unwrap();
+        assert!(writer.remove_if_empty().unwrap());
+        assert!(!Path::new(&path).exists());
+
+        // A file with results, or one that already held results before, is kept
+        let mut writer = ResultWriter::create(&path, OutputFormat::Json, &SearchMode::Keypair, ExistingFile::Refuse).unwrap();
+        writer.write(&result("A1")).unwrap();
+        assert!(!writer.remove_if_empty().unwrap());
+        let mut writer = ResultWriter::create(&path, OutputFormat::Json, &SearchMode::Keypair, ExistingFile::Append).unwrap();
+        assert!(!writer.remove_if_empty().unwrap());
+        assert!(Path::new(&path).exists());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_csv_and_text_append() {
+        let path = temp_path("incremental.csv");
+        let mut writer = ResultWriter::create(&path, OutputFormat::Csv, &SearchMode::Keypair, ExistingFile::Refuse).unwrap();
+        writer.write(&result("A1")).unwrap();
+        writer.write(&result("A2")).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content.lines().collect::<Vec<_>>(), vec!["public_key,private_key,attempts,time_seconds,pattern", "A1,secret,42,1.5,A", "A2,secret,42,1.5,A"]);
+        std::fs::remove_file(&path).unwrap();
+
+        let path = temp_path("incremental.txt");
+        let mut writer = ResultWriter::create(&path, OutputFormat::Text, &SearchMode::Keypair, ExistingFile::Refuse).unwrap();
+        writer.write(&result("A1")).unwrap();
+        writer.write(&result("A2")).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("Address #1\nPublic Key:  A1\n"));
+        assert!(content.contains("Address #2\nPublic Key:  A2\n"));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_csv_quotes_special_characters() {
+        let regex = VanityResult {
+            pattern: "^A{1,2}".to_string(),
+            ..result("A1")
+        };
+        assert_eq!(csv_row(&regex, true), "A1,42,1.5,\"^A{1,2}\"");
+
+        let quoted = VanityResult {
+            pattern: "say \"hi\"\nthere".to_string(),
+            ..result("A1")
+        };
+        assert_eq!(csv_row(&quoted, true), "A1,42,1.5,\"say \"\"hi\"\"\nthere\"");
+    }
+
+    #[test]
+    fn test_keypair_files_do_not_persist_seed_phrases() {
+        assert!(secrets_persisted(&SearchMode::Keypair, false, true));
+        assert!(secrets_persisted(&SearchMode::Mnemonic, true, false));
+        assert!(!secrets_persisted(&SearchMode::Mnemonic, false, true));
+        assert!(!secrets_persisted(&SearchMode::Keypair, false, false));
+    }
+
+    #[test]
+    fn test_redacted_output_has_no_secrets() {
+        let result = result("A1");
+        assert_eq!(csv_header(&SearchMode::Keypair, true), "public_key,attempts,time_seconds,pattern");
+        assert_eq!(csv_row(&result, true), "A1,42,1.5,A");
+
+        let json = serde_json::to_string(&RedactedResult::from(&result)).unwrap();
+        assert!(!json.contains("secret"));
+        assert!(json.starts_with(r#"{"public_key":"A1","pattern":"A","attempts":42,"#));
+    }
+
+    #[test]
+    fn test_mnemonic_results_output() {
+        let mnemonic = VanityResult {
+            key: KeyMaterial::Mnemonic {
+                mnemonic: "abandon ability able".to_string().into(),
+                derivation_path: "m/44'/501'/0'/0'".to_string(),
+            },
+            ..result("A1")
+        };
+
+        assert_eq!(csv_header(&SearchMode::Mnemonic, false), "public_key,mnemonic,derivation_path,attempts,time_seconds,pattern");
+        assert_eq!(csv_row(&mnemonic, false), "A1,abandon ability able,m/44'/501'/0'/0',42,1.5,A");
+        assert!(text_entry(1, &mnemonic).contains("Mnemonic:    abandon ability able\nDerivation:  m/44'/501'/0'/0'\n"));
+
+        // The seed phrase replaces the private key in JSON, and reads back as a mnemonic
+        let json = serde_json::to_value(&mnemonic).unwrap();
+        assert!(json.get("private_key").is_none());
+        assert_eq!(json["mnemonic"], "abandon ability able");
+        let parsed: VanityResult = serde_json::from_value(json).unwrap();
+        assert!(matches!(parsed.key, KeyMaterial::Mnemonic { .. }));
+    }
+}
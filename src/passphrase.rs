@@ -0,0 +1,20 @@
+use anyhow::{anyhow, Result};
+use zeroize::Zeroizing;
+
+/// Environment variable read for the passphrase instead of prompting, for unattended runs
+pub const PASSPHRASE_ENV: &str = "SOLANA_VANITY_PASSPHRASE";
+
+/// Passphrase from `PASSPHRASE_ENV`, or prompted for on the terminal without echo
+///
+/// With `confirm`, an interactively entered passphrase must be typed twice.
+pub fn read_passphrase(confirm: bool) -> Result<Zeroizing<String>> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(Zeroizing::new(passphrase));
+    }
+
+    let passphrase = Zeroizing::new(rpassword::prompt_password("Keystore passphrase: ")?);
+    if confirm && *Zeroizing::new(rpassword::prompt_password("Confirm passphrase: ")?) != *passphrase {
+        return Err(anyhow!("Passphrases do not match"));
+    }
+    Ok(passphrase)
+}
@@ -0,0 +1,622 @@
+use aho_corasick::AhoCorasick;
+use regex::{Regex, RegexBuilder, RegexSet, RegexSetBuilder};
+use regex_syntax::hir::{Class, Hir, HirKind, Look};
+
+use crate::error::{Result, VanityError};
+use crate::prefilter::PrefixFilter;
+use crate::vanity::{get_valid_base58_chars, PatternType, VanityOptions};
+
+/// Length of a typical Base58-encoded Solana public key
+pub(crate) const BASE58_KEY_LENGTH: usize = 44;
+
+/// A set of patterns compiled once up front so the hot loop never re-parses them
+///
+/// Opaque, so the matching engines behind it can change without breaking callers.
+#[derive(Debug, Clone)]
+pub struct PatternMatcher(Matcher);
+
+impl PatternMatcher {
+    pub fn new(options: &VanityOptions) -> Result<Self> {
+        Matcher::new(options).map(Self)
+    }
+
+    /// Cheap check on the raw key bytes, before the key is Base58-encoded
+    ///
+    /// Returning `false` means the key cannot match; `true` means it has to be encoded and
+    /// checked with `matching_patterns`.
+    pub fn may_match(&self, key: &[u8; 32]) -> bool {
+        self.0.may_match(key)
+    }
+
+    /// Whether `may_match` can reject keys before they are encoded, which makes attempts much cheaper
+    pub fn has_prefilter(&self) -> bool {
+        self.0.has_prefilter()
+    }
+
+    /// Check if a public key matches any of the compiled patterns
+    pub fn is_match(&self, public_key: &str) -> bool {
+        !self.matching_patterns(public_key).is_empty()
+    }
+
+    /// Indices of every pattern the public key satisfies, in ascending order
+    pub fn matching_patterns(&self, public_key: &str) -> Vec<usize> {
+        self.0.matching_patterns(public_key)
+    }
+}
+
+/// The matching engine behind a `PatternMatcher`
+#[derive(Debug, Clone)]
+enum Matcher {
+    /// All literal patterns share a single Aho-Corasick automaton
+    Literal {
+        automaton: AhoCorasick,
+        pattern_type: PatternType,
+        max_len: usize,
+        /// Numeric pre-filter on raw key bytes, only available for `StartsWith`
+        prefilter: Option<PrefixFilter>,
+    },
+    Regex(RegexSet),
+    Glob(Vec<Glob>),
+    /// Any of the inner patterns, and the key must also end with `suffix`
+    WithSuffix {
+        inner: Box<Matcher>,
+        suffix: String,
+        case_sensitive: bool,
+    },
+}
+
+impl Matcher {
+    fn new(options: &VanityOptions) -> Result<Self> {
+        let matcher = Self::for_patterns(options)?;
+
+        Ok(match &options.suffix {
+            Some(suffix) => Matcher::WithSuffix {
+                inner: Box::new(matcher),
+                suffix: suffix.clone(),
+                case_sensitive: options.case_sensitive,
+            },
+            None => matcher,
+        })
+    }
+
+    fn for_patterns(options: &VanityOptions) -> Result<Self> {
+        let patterns: Vec<&str> = options.patterns.iter().map(|target| target.pattern.as_str()).collect();
+        let invalid = |message: String| VanityError::InvalidPattern {
+            pattern: patterns.join(", "),
+            message,
+        };
+
+        match options.pattern_type {
+            PatternType::Regex => Ok(Matcher::Regex(
+                RegexSetBuilder::new(&patterns)
+                    .case_insensitive(!options.case_sensitive)
+                    .build()
+                    .map_err(|e| invalid(e.to_string()))?,
+            )),
+            PatternType::Glob => Ok(Matcher::Glob(
+                patterns
+                    .iter()
+                    .map(|pattern| {
+                        Glob::new(pattern, options.case_sensitive).map_err(|message| VanityError::InvalidPattern {
+                            pattern: pattern.to_string(),
+                            message,
+                        })
+                    })
+                    .collect::<Result<_>>()?,
+            )),
+            _ => Ok(Matcher::Literal {
+                automaton: AhoCorasick::builder()
+                    .ascii_case_insensitive(!options.case_sensitive)
+                    .build(&patterns)
+                    .map_err(|e| invalid(e.to_string()))?,
+                pattern_type: options.pattern_type.clone(),
+                max_len: patterns.iter().map(|p| p.len()).max().unwrap_or(0),
+                prefilter: match options.pattern_type {
+                    PatternType::StartsWith => PrefixFilter::new(&patterns, options.case_sensitive),
+                    _ => None,
+                },
+            }),
+        }
+    }
+
+    fn may_match(&self, key: &[u8; 32]) -> bool {
+        match self {
+            Matcher::Literal {
+                prefilter: Some(prefilter),
+                ..
+            } => prefilter.may_match(key),
+            Matcher::WithSuffix { inner, .. } => inner.may_match(key),
+            _ => true,
+        }
+    }
+
+    fn has_prefilter(&self) -> bool {
+        match self {
+            Matcher::Literal { prefilter, .. } => prefilter.is_some(),
+            Matcher::WithSuffix { inner, .. } => inner.has_prefilter(),
+            _ => false,
+        }
+    }
+
+    fn matching_patterns(&self, public_key: &str) -> Vec<usize> {
+        match self {
+            Matcher::Literal {
+                automaton,
+                pattern_type,
+                max_len,
+                ..
+            } => {
+                // Only the first/last `max_len` characters can hold a prefix/suffix match
+                let window = (*max_len).min(public_key.len());
+                let haystack = match pattern_type {
+                    PatternType::StartsWith => &public_key[..window],
+                    PatternType::EndsWith => &public_key[public_key.len() - window..],
+                    _ => public_key,
+                };
+
+                let mut matched: Vec<usize> = automaton
+                    .find_overlapping_iter(haystack)
+                    .filter(|m| match pattern_type {
+                        PatternType::StartsWith => m.start() == 0,
+                        PatternType::EndsWith => m.end() == haystack.len(),
+                        _ => true,
+                    })
+                    .map(|m| m.pattern().as_usize())
+                    .collect();
+                matched.sort_unstable();
+                matched.dedup();
+                matched
+            }
+            Matcher::Regex(set) => set.matches(public_key).into_iter().collect(),
+            Matcher::Glob(globs) => globs
+                .iter()
+                .enumerate()
+                .filter(|(_, glob)| glob.is_match(public_key))
+                .map(|(index, _)| index)
+                .collect(),
+            Matcher::WithSuffix {
+                inner,
+                suffix,
+                case_sensitive,
+            } => {
+                // The suffix check is cheap, so do it before running the inner matcher
+                let ends_with_suffix = public_key.len() >= suffix.len() && {
+                    let tail = &public_key[public_key.len() - suffix.len()..];
+                    if *case_sensitive {
+                        tail == suffix
+                    } else {
+                        tail.eq_ignore_ascii_case(suffix)
+                    }
+                };
+
+                if ends_with_suffix {
+                    inner.matching_patterns(public_key)
+                } else {
+                    Vec::new()
+                }
+            }
+        }
+    }
+}
+
+/// One position of a glob pattern: a set of accepted ASCII characters as a bitmask
+type CharMask = u128;
+
+/// A glob pattern anchored at the start of the key
+///
+/// `?` matches any Base58 character, `[...]` a character class (`[A-F]` ranges and
+/// `[!...]` negation are supported) and `*` any run of characters. Anything after the
+/// pattern is ignored, so a leading `*` lets the whole pattern float.
+#[derive(Debug, Clone)]
+pub struct Glob {
+    /// Fixed-length segments separated by `*`; only the first is anchored
+    segments: Vec<Vec<CharMask>>,
+}
+
+impl Glob {
+    pub fn new(pattern: &str, case_sensitive: bool) -> Result<Self, String> {
+        let alphabet = base58_mask();
+        let mut segments = vec![Vec::new()];
+        let mut chars = pattern.chars();
+
+        while let Some(c) = chars.next() {
+            let mask = match c {
+                '*' => {
+                    segments.push(Vec::new());
+                    continue;
+                }
+                '?' => alphabet,
+                '[' => parse_class(&mut chars, case_sensitive)?,
+                c if is_base58_char(c) => char_mask(c, case_sensitive),
+                c => return Err(format!("Invalid character '{}' in glob", c)),
+            } & alphabet;
+
+            if mask == 0 {
+                return Err("Character class matches no Base58 characters".to_string());
+            }
+            segments.last_mut().unwrap().push(mask);
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Check if a public key matches the glob
+    pub fn is_match(&self, public_key: &str) -> bool {
+        let key = public_key.as_bytes();
+
+        // The first segment is anchored; later ones take their leftmost match
+        if !segment_matches_at(&self.segments[0], key, 0) {
+            return false;
+        }
+
+        let mut cursor = self.segments[0].len();
+        for segment in &self.segments[1..] {
+            match (cursor..=key.len().saturating_sub(segment.len())).find(|&i| segment_matches_at(segment, key, i)) {
+                Some(start) => cursor = start + segment.len(),
+                None => return false,
+            }
+        }
+
+        true
+    }
+
+    /// Estimate the probability that a random key matches the glob
+    pub fn estimate_probability(&self) -> f64 {
+        let alphabet_size = get_valid_base58_chars().len() as f64;
+        let segment_probability = |segment: &Vec<CharMask>| -> f64 {
+            segment
+                .iter()
+                .map(|mask| mask.count_ones() as f64 / alphabet_size)
+                .product()
+        };
+
+        let mut probability = segment_probability(&self.segments[0]);
+        let mut consumed = self.segments[0].len();
+
+        // A floating segment gets a chance at every position left in the key
+        for segment in &self.segments[1..] {
+            let positions = BASE58_KEY_LENGTH.saturating_sub(consumed + segment.len()) + 1;
+            probability *= (segment_probability(segment) * positions as f64).min(1.0);
+            consumed += segment.len();
+        }
+
+        probability
+    }
+}
+
+fn segment_matches_at(segment: &[CharMask], key: &[u8], start: usize) -> bool {
+    start + segment.len() <= key.len()
+        && segment
+            .iter()
+            .zip(&key[start..])
+            .all(|(mask, &byte)| byte < 128 && mask & (1 << byte) != 0)
+}
+
+/// Parse the body of a `[...]` class, consuming the closing bracket
+fn parse_class(chars: &mut std::str::Chars, case_sensitive: bool) -> Result<CharMask, String> {
+    let mut mask: CharMask = 0;
+    let mut negated = false;
+    let mut previous: Option<char> = None;
+    let mut first = true;
+
+    loop {
+        let c = chars.next().ok_or("Unclosed '[' in glob")?;
+        match c {
+            ']' => break,
+            '!' | '^' if first => negated = true,
+            '-' if previous.is_some() => {
+                let start = previous.take().unwrap();
+                let end = chars.next().ok_or("Unclosed '[' in glob")?;
+                if end == ']' || end < start {
+                    return Err(format!("Invalid range '{}-{}' in glob", start, end));
+                }
+                for c in start..=end {
+                    mask |= char_mask(c, case_sensitive);
+                }
+            }
+            c if c.is_ascii() => {
+                mask |= char_mask(c, case_sensitive);
+                previous = Some(c);
+            }
+            c => return Err(format!("Invalid character '{}' in glob", c)),
+        }
+        first = false;
+    }
+
+    Ok(if negated { !mask & base58_mask() } else { mask })
+}
+
+fn char_mask(c: char, case_sensitive: bool) -> CharMask {
+    if !c.is_ascii() {
+        return 0;
+    }
+    if case_sensitive {
+        1 << c as u32
+    } else {
+        (1 << c.to_ascii_lowercase() as u32) | (1 << c.to_ascii_uppercase() as u32)
+    }
+}
+
+fn base58_mask() -> CharMask {
+    get_valid_base58_chars().chars().fold(0, |mask, c| mask | char_mask(c, true))
+}
+
+/// Validate a glob pattern's syntax and characters
+pub fn validate_glob_pattern(pattern: &str, case_sensitive: bool) -> Result<(), String> {
+    Glob::new(pattern, case_sensitive).map(|_| ())
+}
+
+/// Compile a regex pattern, honouring the case sensitivity flag
+pub fn compile_regex(pattern: &str, case_sensitive: bool) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(!case_sensitive)
+        .build()
+}
+
+/// Validate a regex pattern: it must compile and be able to match at least one Base58 string
+pub fn validate_regex_pattern(pattern: &str, case_sensitive: bool) -> Result<(), String> {
+    let hir = parse_regex(pattern, case_sensitive)?;
+
+    if !can_match_base58(&hir) {
+        return Err("Regex can never match a Base58 address".to_string());
+    }
+    match hir.properties().minimum_len() {
+        Some(length) if length > BASE58_KEY_LENGTH => Err(format!(
+            "Regex needs at least {} characters, but a Base58 address has at most {}",
+            length, BASE58_KEY_LENGTH
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Estimate the probability that a random Base58 public key matches a regex
+pub fn estimate_regex_probability(pattern: &str, case_sensitive: bool) -> f64 {
+    let hir = match parse_regex(pattern, case_sensitive) {
+        Ok(hir) => hir,
+        Err(_) => return 0.0,
+    };
+
+    let probability = position_probability(&hir);
+    let anchored = hir.properties().look_set_prefix().contains(Look::Start)
+        || hir.properties().look_set_suffix().contains(Look::End);
+
+    if anchored {
+        probability
+    } else {
+        // An unanchored regex gets a chance at every offset of the key
+        (probability * BASE58_KEY_LENGTH as f64).min(1.0)
+    }
+}
+
+fn parse_regex(pattern: &str, case_sensitive: bool) -> Result<Hir, String> {
+    // Surface the same error message the matcher itself would produce
+    compile_regex(pattern, case_sensitive).map_err(|e| e.to_string())?;
+
+    regex_syntax::ParserBuilder::new()
+        .case_insensitive(!case_sensitive)
+        .build()
+        .parse(pattern)
+        .map_err(|e| e.to_string())
+}
+
+/// Check whether any string over the Base58 alphabet can satisfy the expression
+fn can_match_base58(hir: &Hir) -> bool {
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => true,
+        HirKind::Literal(literal) => match std::str::from_utf8(&literal.0) {
+            Ok(text) => text.chars().all(is_base58_char),
+            Err(_) => false,
+        },
+        HirKind::Class(class) => class_size(class) > 0,
+        HirKind::Repetition(repetition) => repetition.min == 0 || can_match_base58(&repetition.sub),
+        HirKind::Capture(capture) => can_match_base58(&capture.sub),
+        HirKind::Concat(parts) => parts.iter().all(can_match_base58),
+        HirKind::Alternation(branches) => branches.iter().any(can_match_base58),
+    }
+}
+
+/// Probability that the expression matches starting at one fixed position of a random key
+fn position_probability(hir: &Hir) -> f64 {
+    let alphabet_size = get_valid_base58_chars().len() as f64;
+
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => 1.0,
+        HirKind::Literal(literal) => match std::str::from_utf8(&literal.0) {
+            Ok(text) if text.chars().all(is_base58_char) => {
+                (1.0 / alphabet_size).powi(text.chars().count() as i32)
+            }
+            _ => 0.0,
+        },
+        HirKind::Class(class) => class_size(class) as f64 / alphabet_size,
+        HirKind::Repetition(repetition) => {
+            position_probability(&repetition.sub).powi(repetition.min as i32)
+        }
+        HirKind::Capture(capture) => position_probability(&capture.sub),
+        HirKind::Concat(parts) => parts.iter().map(position_probability).product(),
+        HirKind::Alternation(branches) => {
+            branches.iter().map(position_probability).sum::<f64>().min(1.0)
+        }
+    }
+}
+
+/// Number of Base58 characters accepted by a character class
+fn class_size(class: &Class) -> usize {
+    get_valid_base58_chars()
+        .chars()
+        .filter(|&c| match class {
+            Class::Unicode(unicode) => unicode
+                .ranges()
+                .iter()
+                .any(|range| range.start() <= c && c <= range.end()),
+            Class::Bytes(bytes) => bytes
+                .ranges()
+                .iter()
+                .any(|range| range.start() <= c as u8 && c as u8 <= range.end()),
+        })
+        .count()
+}
+
+fn is_base58_char(c: char) -> bool {
+    get_valid_base58_chars().contains(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keygen::SearchMode;
+    use crate::vanity::PatternTarget;
+    use std::time::Duration;
+
+    fn options(patterns: &[&str], pattern_type: PatternType, case_sensitive: bool) -> VanityOptions {
+        VanityOptions {
+            patterns: patterns.iter().map(|p| PatternTarget::new(p, 1)).collect(),
+            pattern_type,
+            case_sensitive,
+            suffix: None,
+            max_attempts: 1000,
+            max_time: Duration::from_secs(1),
+            mode: SearchMode::Keypair,
+        }
+    }
+
+    #[test]
+    fn test_multi_pattern_matching() {
+        let public_key = "ABC123def456GHi789jkm";
+
+        let matcher = PatternMatcher::new(&options(&["XYZ", "AB", "ABC", "def"], PatternType::StartsWith, true)).unwrap();
+        assert_eq!(matcher.matching_patterns(public_key), vec![1, 2]);
+
+        let matcher = PatternMatcher::new(&options(&["jkm", "789", "9jkm"], PatternType::EndsWith, true)).unwrap();
+        assert_eq!(matcher.matching_patterns(public_key), vec![0, 2]);
+
+        let matcher = PatternMatcher::new(&options(&["def", "GHi", "xyz"], PatternType::Contains, true)).unwrap();
+        assert_eq!(matcher.matching_patterns(public_key), vec![0, 1]);
+
+        let matcher = PatternMatcher::new(&options(&["abc", "DEF"], PatternType::Contains, false)).unwrap();
+        assert_eq!(matcher.matching_patterns(public_key), vec![0, 1]);
+
+        let matcher = PatternMatcher::new(&options(&["^ABC[1-9]", "jkm$", "^XYZ"], PatternType::Regex, true)).unwrap();
+        assert_eq!(matcher.matching_patterns(public_key), vec![0, 1]);
+        assert!(!matcher.is_match("XY"));
+    }
+
+    #[test]
+    fn test_glob_matching() {
+        let glob = Glob::new("AB?D", true).unwrap();
+        assert!(glob.is_match("ABcD123"));
+        assert!(glob.is_match("AB9Dxyz"));
+        assert!(!glob.is_match("ABcE123"));
+        assert!(!glob.is_match("xABcD12"));
+
+        let glob = Glob::new("[ABC]x*9", true).unwrap();
+        assert!(glob.is_match("Bxyz9"));
+        assert!(glob.is_match("Cx9"));
+        assert!(!glob.is_match("Dxyz9"));
+        assert!(!glob.is_match("Bxyz8"));
+
+        let glob = Glob::new("[A-C][!1-9]", true).unwrap();
+        assert!(glob.is_match("Ba"));
+        assert!(!glob.is_match("B1"));
+        assert!(!glob.is_match("Da"));
+
+        let glob = Glob::new("*RUST", false).unwrap();
+        assert!(glob.is_match("123rust456"));
+
+        let matcher = PatternMatcher::new(&options(&["A?C", "*jkm"], PatternType::Glob, true)).unwrap();
+        assert_eq!(matcher.matching_patterns("ABC123def456GHi789jkm"), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_validate_glob_pattern() {
+        assert!(validate_glob_pattern("AB?D", true).is_ok());
+        assert!(validate_glob_pattern("[ABC]x*9", true).is_ok());
+        assert!(validate_glob_pattern("[0O]x", false).is_ok()); // 'o' is still valid
+
+        assert!(validate_glob_pattern("SOL?", true).is_err());
+        assert!(validate_glob_pattern("[0OIl]", true).is_err());
+        assert!(validate_glob_pattern("[ABC", true).is_err());
+        assert!(validate_glob_pattern("[C-A]", true).is_err());
+    }
+
+    #[test]
+    fn test_glob_probability_estimation() {
+        let alphabet_size = 58.0;
+
+        // Wildcards don't constrain anything
+        let wildcard = Glob::new("A??D", true).unwrap().estimate_probability();
+        assert!((wildcard - 1.0 / (alphabet_size * alphabet_size)).abs() < 1e-12);
+
+        let class = Glob::new("[ABC]", true).unwrap().estimate_probability();
+        assert!((class - 3.0 / alphabet_size).abs() < 1e-12);
+
+        assert!(Glob::new("A*9", true).unwrap().estimate_probability() > Glob::new("A9", true).unwrap().estimate_probability());
+    }
+
+    #[test]
+    fn test_prefix_suffix_matching() {
+        let mut prefix_suffix = options(&["Byte", "Rust"], PatternType::StartsWith, true);
+        prefix_suffix.suffix = Some("Dev".to_string());
+        let matcher = PatternMatcher::new(&prefix_suffix).unwrap();
+
+        assert_eq!(matcher.matching_patterns("Byte123abcDev"), vec![0]);
+        assert_eq!(matcher.matching_patterns("Rust123abcDev"), vec![1]);
+        assert!(!matcher.is_match("Byte123abcDex")); // Wrong suffix
+        assert!(!matcher.is_match("Bite123abcDev")); // Wrong prefix
+        assert!(!matcher.is_match("Byte123abcdev")); // Case sensitive
+
+        // The prefix pre-filter still applies underneath the suffix check
+        let other_prefix = bs58::decode("Zzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzz").into_vec().unwrap();
+        assert!(!matcher.may_match(&other_prefix.try_into().unwrap()));
+
+        prefix_suffix.case_sensitive = false;
+        let matcher = PatternMatcher::new(&prefix_suffix).unwrap();
+        assert!(matcher.is_match("byte123abcdev"));
+        assert!(!matcher.is_match("Dev"));
+    }
+
+    #[test]
+    fn test_regex_matching() {
+        let regex = compile_regex("^(SoL|Dex)[1-9]{2}", true).unwrap();
+        assert!(regex.is_match("SoL42abc"));
+        assert!(regex.is_match("Dex99xyz"));
+        assert!(!regex.is_match("sol42abc"));
+        assert!(!regex.is_match("xSoL42"));
+
+        let regex = compile_regex("^(SoL|Dex)[1-9]{2}", false).unwrap();
+        assert!(regex.is_match("sol42abc"));
+    }
+
+    #[test]
+    fn test_validate_regex_pattern() {
+        assert!(validate_regex_pattern("^(SoL|Dex)[1-9]{2}", true).is_ok());
+        assert!(validate_regex_pattern("^ABC", true).is_ok());
+        assert!(validate_regex_pattern("(O|A)", true).is_ok()); // One branch is still valid
+        assert!(validate_regex_pattern("0?ABC", true).is_ok()); // Optional invalid char
+
+        assert!(validate_regex_pattern("^SOL", true).is_err()); // Capital O
+        assert!(validate_regex_pattern("[0OIl]", true).is_err());
+        assert!(validate_regex_pattern("^0x", true).is_err());
+        assert!(validate_regex_pattern("(unclosed", true).is_err());
+        assert!(validate_regex_pattern("^[1-9]{50}", true).is_err()); // Longer than any address
+        assert!(validate_regex_pattern("^[1-9]{44}$", true).is_ok());
+
+        // Case insensitive matching lets "SOL" match "SoL"
+        assert!(validate_regex_pattern("^SOL", false).is_ok());
+    }
+
+    #[test]
+    fn test_regex_probability_estimation() {
+        let anchored = estimate_regex_probability("^A", true);
+        assert!((anchored - 1.0 / 58.0).abs() < 1e-12);
+
+        let class = estimate_regex_probability("^[1-9]", true);
+        assert!((class - 9.0 / 58.0).abs() < 1e-12);
+
+        let alternation = estimate_regex_probability("^(AB|CD)", true);
+        assert!((alternation - 2.0 / (58.0 * 58.0)).abs() < 1e-12);
+
+        // Unanchored patterns get a chance at every position
+        assert!(estimate_regex_probability("ABC", true) > estimate_regex_probability("^ABC", true));
+        assert_eq!(estimate_regex_probability("^[0O]", true), 0.0);
+    }
+}
@@ -0,0 +1,263 @@
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+
+use crate::vanity::get_valid_base58_chars;
+
+/// Longest Base58 encoding of a 32-byte public key
+const MAX_ENCODED_LENGTH: usize = 44;
+
+/// Cap on case variants expanded for case-insensitive prefixes
+const MAX_PREFIX_VARIANTS: usize = 4096;
+
+/// Numeric pre-filter for `StartsWith` patterns
+///
+/// Every prefix corresponds to a handful of 256-bit intervals (one per possible
+/// encoded length), so raw public key bytes can be compared against those bounds
+/// before paying for Base58 encoding. The filter never rejects a matching key;
+/// the rare candidates it lets through are still checked against the real pattern.
+#[derive(Debug, Clone)]
+pub struct PrefixFilter {
+    /// Sorted, non-overlapping, inclusive big-endian intervals
+    intervals: Vec<([u8; 32], [u8; 32])>,
+}
+
+impl PrefixFilter {
+    /// Build a filter accepting any key that starts with one of `prefixes`
+    ///
+    /// Returns `None` when no useful filter exists, e.g. for prefixes starting with
+    /// `1` (which encode leading zero bytes) or too many case-insensitive variants.
+    pub fn new(prefixes: &[&str], case_sensitive: bool) -> Option<Self> {
+        let mut variants = Vec::new();
+        for prefix in prefixes {
+            if prefix.is_empty() || prefix.starts_with('1') {
+                return None;
+            }
+            variants.extend(case_variants(prefix, case_sensitive, MAX_PREFIX_VARIANTS)?);
+            if variants.len() > MAX_PREFIX_VARIANTS {
+                return None;
+            }
+        }
+
+        let mut intervals: Vec<(BigUint, BigUint)> = variants
+            .iter()
+            .flat_map(|variant| prefix_intervals(variant))
+            .collect();
+        intervals.sort();
+
+        // Merge overlapping or adjacent intervals
+        let mut merged: Vec<(BigUint, BigUint)> = Vec::new();
+        for (low, high) in intervals {
+            match merged.last_mut() {
+                Some((_, last_high)) if low <= &*last_high + 1u32 => {
+                    if high > *last_high {
+                        *last_high = high;
+                    }
+                }
+                _ => merged.push((low, high)),
+            }
+        }
+
+        Some(Self {
+            intervals: merged
+                .iter()
+                .map(|(low, high)| (to_key_bytes(low), to_key_bytes(high)))
+                .collect(),
+        })
+    }
+
+    /// Check whether a raw public key could start with one of the prefixes
+    pub fn may_match(&self, key: &[u8; 32]) -> bool {
+        let index = self.intervals.partition_point(|(_, high)| high < key);
+        self.intervals
+            .get(index)
+            .is_some_and(|(low, _)| low <= key)
+    }
+}
+
+/// Every 256-bit interval of numbers whose Base58 encoding starts with `prefix`
+///
+/// A number with an `L`-character encoding starts with the `k`-character prefix
+/// `p` exactly when it lies in `[p * 58^(L-k), (p + 1) * 58^(L-k))`.
+pub fn prefix_intervals(prefix: &str) -> Vec<(BigUint, BigUint)> {
+    let base = BigUint::from(58u32);
+    let key_max = (BigUint::from(1u32) << 256) - 1u32;
+    let value = base58_value(prefix);
+
+    (prefix.len()..=MAX_ENCODED_LENGTH)
+        .filter_map(|length| {
+            let scale = base.pow((length - prefix.len()) as u32);
+            let low = &value * &scale;
+            if low > key_max {
+                return None;
+            }
+            let high = ((&value + 1u32) * &scale - 1u32).min(key_max.clone());
+            Some((low, high))
+        })
+        .collect()
+}
+
+/// Exact probability that a uniformly random public key starts with `prefix`
+///
+/// Base58 digits of a 256-bit number are far from uniform: the leading digit is
+/// bounded by 2^256 / 58^43, and keys encode to either 43 or 44 characters. So
+/// rather than assuming 1/58 per character, this measures the share of the key
+/// space covered by the prefix intervals. Each leading `1` stands for a zero byte.
+///
+/// Returns `None` when there are too many case-insensitive spellings to enumerate.
+pub fn prefix_probability(prefix: &str, case_sensitive: bool) -> Option<f64> {
+    let rest = prefix.trim_start_matches('1');
+    let zero_bytes = prefix.len() - rest.len();
+    if zero_bytes >= 32 {
+        return Some(0.0);
+    }
+    if rest.is_empty() {
+        // Only the leading zero bytes are constrained
+        return Some(2f64.powi(-8 * zero_bytes as i32));
+    }
+
+    // Exactly `zero_bytes` leading zeros, so the first non-zero byte follows them
+    let lowest = BigUint::from(1u32) << (8 * (31 - zero_bytes));
+    let highest = (BigUint::from(1u32) << (8 * (32 - zero_bytes))) - 1u32;
+
+    let mut covered = BigUint::from(0u32);
+    for variant in case_variants(rest, case_sensitive, MAX_PREFIX_VARIANTS)? {
+        for (low, high) in prefix_intervals(&variant) {
+            let low = low.max(lowest.clone());
+            let high = high.min(highest.clone());
+            if low <= high {
+                covered += high - low + 1u32;
+            }
+        }
+    }
+
+    Some(covered.to_f64()? / 2f64.powi(256))
+}
+
+/// Numeric value of a Base58 string, most significant digit first
+fn base58_value(digits: &str) -> BigUint {
+    let alphabet = get_valid_base58_chars();
+    digits.chars().fold(BigUint::from(0u32), |value, c| {
+        value * 58u32 + alphabet.find(c).unwrap_or(0) as u32
+    })
+}
+
+/// All spellings of `prefix` accepted under the given case sensitivity
+fn case_variants(prefix: &str, case_sensitive: bool, limit: usize) -> Option<Vec<String>> {
+    let alphabet = get_valid_base58_chars();
+    let mut variants = vec![String::new()];
+
+    for c in prefix.chars() {
+        let mut options = vec![c];
+        if !case_sensitive {
+            options = vec![c.to_ascii_uppercase(), c.to_ascii_lowercase()];
+            options.dedup();
+        }
+        options.retain(|&option| alphabet.contains(option));
+
+        variants = variants
+            .iter()
+            .flat_map(|variant| options.iter().map(move |&option| format!("{}{}", variant, option)))
+            .collect();
+        if variants.len() > limit {
+            return None;
+        }
+    }
+
+    Some(variants)
+}
+
+/// Big-endian 32-byte representation of a number below 2^256
+fn to_key_bytes(value: &BigUint) -> [u8; 32] {
+    let bytes = value.to_bytes_be();
+    let mut key = [0u8; 32];
+    key[32 - bytes.len()..].copy_from_slice(&bytes);
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::{Keypair, Signer};
+
+    #[test]
+    fn test_prefix_filter_agrees_with_encoding() {
+        let filters = [
+            ("A", PrefixFilter::new(&["A"], true).unwrap(), true),
+            ("z", PrefixFilter::new(&["z"], true).unwrap(), true),
+            ("5", PrefixFilter::new(&["5"], true).unwrap(), true),
+            ("a", PrefixFilter::new(&["a"], false).unwrap(), false),
+        ];
+
+        for _ in 0..10_000 {
+            let bytes = Keypair::new().pubkey().to_bytes();
+            let encoded = bs58::encode(&bytes).into_string();
+
+            for (prefix, filter, case_sensitive) in &filters {
+                let matches = if *case_sensitive {
+                    encoded.starts_with(prefix)
+                } else {
+                    encoded.to_lowercase().starts_with(prefix)
+                };
+
+                // Never reject a real match, and be exact for keys without a leading zero byte
+                if matches || bytes[0] != 0 {
+                    assert_eq!(filter.may_match(&bytes), matches, "{} vs {}", prefix, encoded);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_prefix_filter_boundaries() {
+        let filter = PrefixFilter::new(&["BYTE"], true).unwrap();
+
+        let lowest = bs58::decode("BYTE1111111111111111111111111111111111111111").into_vec().unwrap();
+        let highest = bs58::decode("BYTEzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzz").into_vec().unwrap();
+        assert!(filter.may_match(&lowest.try_into().unwrap()));
+        assert!(filter.may_match(&highest.try_into().unwrap()));
+
+        let below = bs58::decode("BYTDzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzz").into_vec().unwrap();
+        let above = bs58::decode("BYTF1111111111111111111111111111111111111111").into_vec().unwrap();
+        assert!(!filter.may_match(&below.try_into().unwrap()));
+        assert!(!filter.may_match(&above.try_into().unwrap()));
+    }
+
+    #[test]
+    fn test_prefix_probability_models_leading_digit_skew() {
+        // Every key starts with exactly one character
+        let total: f64 = get_valid_base58_chars()
+            .chars()
+            .map(|c| prefix_probability(&c.to_string(), true).unwrap())
+            .sum();
+        assert!((total - 1.0).abs() < 1e-9, "{}", total);
+
+        // Low digits lead 44-character keys; high ones only lead the rare 43-character keys
+        let a = prefix_probability("A", true).unwrap();
+        let z = prefix_probability("z", true).unwrap();
+        assert!(a > 3.0 / 58.0);
+        assert!(z < 0.1 / 58.0);
+        assert_eq!(prefix_probability("1", true), Some(1.0 / 256.0));
+        let either_case = prefix_probability("a", true).unwrap() + prefix_probability("A", true).unwrap();
+        assert!((prefix_probability("a", false).unwrap() - either_case).abs() < 1e-15);
+
+        // Measured hit rates agree with the estimates
+        let samples = 20_000;
+        let (mut a_hits, mut z_hits) = (0, 0);
+        for _ in 0..samples {
+            let encoded = Keypair::new().pubkey().to_string();
+            a_hits += encoded.starts_with('A') as usize;
+            z_hits += encoded.starts_with('z') as usize;
+        }
+        let a_measured = a_hits as f64 / samples as f64;
+        assert!((a_measured - a).abs() < 0.01, "A: measured {} vs estimated {}", a_measured, a);
+        assert!((z_hits as f64) < 10.0 * z * samples as f64 + 10.0, "z: {} hits vs estimated {}", z_hits, z);
+    }
+
+    #[test]
+    fn test_prefix_filter_unsupported() {
+        assert!(PrefixFilter::new(&["1A"], true).is_none());
+        assert!(PrefixFilter::new(&[""], true).is_none());
+        assert!(PrefixFilter::new(&["abcdefghijkmnopq"], false).is_none()); // 2^16 case variants
+        assert!(PrefixFilter::new(&["ABC", "XYZ"], false).is_some());
+    }
+}
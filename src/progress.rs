@@ -0,0 +1,320 @@
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use serde::Serialize;
+use std::io::Write;
+use std::sync::Mutex;
+
+use crate::vanity::{SearchProgress, StopReason, VanityGenerator, VanityOptions, VanityResult};
+
+/// Receives the lifecycle of every search a `VanityGenerator` runs
+///
+/// Methods are called from worker threads, so implementations must be cheap and
+/// thread-safe. Every method does nothing by default.
+pub trait ProgressReporter: Send + Sync {
+    /// The workers are about to start
+    fn started(&self, _options: &VanityOptions, _thread_count: usize) {}
+
+    /// Called a few times a second while the search runs
+    fn progress(&self, _progress: &SearchProgress) {}
+
+    /// A result was found; `progress` already counts it
+    fn found(&self, _result: &VanityResult, _progress: &SearchProgress) {}
+
+    /// The workers have stopped
+    fn finished(&self, _total_attempts: u64, _stop_reason: StopReason) {}
+}
+
+/// Reports nothing; what a generator uses unless given another reporter
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SilentReporter;
+
+impl ProgressReporter for SilentReporter {}
+
+/// An interactive progress bar on stderr, counting found addresses
+pub struct IndicatifReporter {
+    bar: ProgressBar,
+}
+
+impl IndicatifReporter {
+    pub fn new() -> Self {
+        // Hidden until a search starts and the number of addresses is known
+        let bar = ProgressBar::hidden();
+        bar.set_style(
+            ProgressStyle::default_bar()
+                .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} addresses ({percent}%) {msg}")
+                .unwrap()
+                .progress_chars("#>-"),
+        );
+        Self { bar }
+    }
+}
+
+impl Default for IndicatifReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressReporter for IndicatifReporter {
+    fn started(&self, options: &VanityOptions, _thread_count: usize) {
+        self.bar.reset();
+        self.bar.set_length(options.patterns.iter().map(|target| target.count as u64).sum());
+        self.bar.set_draw_target(ProgressDrawTarget::stderr());
+    }
+
+    fn progress(&self, progress: &SearchProgress) {
+        self.bar.set_message(format!("{:.0} attempts/sec", progress.attempts_per_second()));
+    }
+
+    fn found(&self, _result: &VanityResult, progress: &SearchProgress) {
+        self.bar.set_position(progress.found as u64);
+    }
+
+    fn finished(&self, _total_attempts: u64, _stop_reason: StopReason) {
+        self.bar.finish_with_message("Generation complete!");
+    }
+}
+
+/// One JSON object per line for every event, for programs that wrap the search
+///
+/// Emits `started`, periodic `heartbeat`, `found` and `finished` events. Found events
+/// carry only the public key and pattern, never key material.
+pub struct JsonLinesReporter<W> {
+    writer: Mutex<W>,
+    /// Every pattern of the running search and how many of its results are still missing
+    targets: Mutex<Vec<RemainingTarget>>,
+}
+
+/// A pattern still being searched for, as tracked for the ETA
+struct RemainingTarget {
+    label: String,
+    /// Chance that one attempt matches this pattern, estimated when the search starts
+    probability: f64,
+    remaining: usize,
+}
+
+impl<W: Write + Send> JsonLinesReporter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+            targets: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Seconds until the remaining addresses are expected at the current speed
+    ///
+    /// Patterns that already have their results no longer count; of the others, the one
+    /// expected to finish last sets the ETA.
+    fn eta_secs(&self, progress: &SearchProgress) -> Option<f64> {
+        let attempts_per_second = progress.attempts_per_second();
+        if attempts_per_second <= 0.0 {
+            return None;
+        }
+
+        let mut eta: f64 = 0.0;
+        for target in self.targets.lock().unwrap().iter().filter(|target| target.remaining > 0) {
+            if target.probability <= 0.0 {
+                return None;
+            }
+            eta = eta.max(target.remaining as f64 / (target.probability * attempts_per_second));
+        }
+        Some(eta)
+    }
+
+    fn emit(&self, event: &JsonEvent) {
+        // One write per line, so lines stay whole even when others share the stream
+        let mut line = serde_json::to_vec(event).expect("events serialize to JSON");
+        line.push(b'\n');
+
+        // Progress is best-effort; a closed pipe must not stop the search
+        let mut writer = self.writer.lock().unwrap();
+        let _ = writer.write_all(&line).and_then(|_| writer.flush());
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum JsonEvent<'a> {
+    Started {
+        patterns: Vec<String>,
+        requested: usize,
+        threads: usize,
+        /// Expected attempts per address; null when no key can match
+        expected_attempts: Option<u64>,
+    },
+    Heartbeat {
+        attempts: u64,
+        found: usize,
+        requested: usize,
+        elapsed_secs: f64,
+        attempts_per_second: f64,
+        /// Expected seconds until every address is found; null until the speed is known
+        eta_secs: Option<f64>,
+    },
+    Found {
+        public_key: &'a str,
+        pattern: &'a str,
+        attempts: u64,
+        found: usize,
+        requested: usize,
+        elapsed_secs: f64,
+    },
+    Finished {
+        reason: StopReason,
+        total_attempts: u64,
+    },
+}
+
+impl<W: Write + Send> ProgressReporter for JsonLinesReporter<W> {
+    fn started(&self, options: &VanityOptions, thread_count: usize) {
+        let estimator = VanityGenerator::new();
+        let probability = estimator.estimate_probability(options);
+        *self.targets.lock().unwrap() = options
+            .patterns
+            .iter()
+            .enumerate()
+            .map(|(index, target)| RemainingTarget {
+                label: options.pattern_label(index),
+                probability: estimator.estimate_pattern_probability(&target.pattern, options),
+                remaining: target.count,
+            })
+            .collect();
+
+        self.emit(&JsonEvent::Started {
+            patterns: (0..options.patterns.len()).map(|index| options.pattern_label(index)).collect(),
+            requested: options.patterns.iter().map(|target| target.count).sum(),
+            threads: thread_count,
+            expected_attempts: (probability > 0.0).then(|| estimator.estimate_expected_attempts(options)),
+        });
+    }
+
+    fn progress(&self, progress: &SearchProgress) {
+        self.emit(&JsonEvent::Heartbeat {
+            attempts: progress.attempts,
+            found: progress.found,
+            requested: progress.requested,
+            elapsed_secs: progress.elapsed.as_secs_f64(),
+            attempts_per_second: progress.attempts_per_second(),
+            eta_secs: self.eta_secs(progress),
+        });
+    }
+
+    fn found(&self, result: &VanityResult, progress: &SearchProgress) {
+        // Results are credited to the first pattern still short of its count, as the search does
+        let mut targets = self.targets.lock().unwrap();
+        if let Some(target) = targets.iter_mut().find(|target| target.label == result.pattern && target.remaining > 0) {
+            target.remaining -= 1;
+        }
+        drop(targets);
+
+        self.emit(&JsonEvent::Found {
+            public_key: &result.public_key,
+            pattern: &result.pattern,
+            attempts: result.attempts,
+            found: progress.found,
+            requested: progress.requested,
+            elapsed_secs: result.time_elapsed.as_secs_f64(),
+        });
+    }
+
+    fn finished(&self, total_attempts: u64, stop_reason: StopReason) {
+        self.emit(&JsonEvent::Finished {
+            reason: stop_reason,
+            total_attempts,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    /// A writer the test can read back after the reporter is done with it
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn target(label: &str, probability: f64, remaining: usize) -> RemainingTarget {
+        RemainingTarget {
+            label: label.to_string(),
+            probability,
+            remaining,
+        }
+    }
+
+    fn progress(found: usize, attempts: u64) -> SearchProgress {
+        SearchProgress {
+            attempts,
+            found,
+            requested: 3,
+            elapsed: std::time::Duration::from_secs(2),
+        }
+    }
+
+    #[test]
+    fn test_json_lines_heartbeat_eta() {
+        let reporter = JsonLinesReporter::new(Vec::new());
+        *reporter.targets.lock().unwrap() = vec![target("A", 0.01, 2)];
+
+        // 1000 attempts/sec at 1% finds 10 addresses a second
+        assert_eq!(reporter.eta_secs(&progress(1, 2000)), Some(0.2));
+        assert_eq!(reporter.eta_secs(&progress(0, 0)), None);
+        reporter.targets.lock().unwrap()[0].remaining = 0;
+        assert_eq!(reporter.eta_secs(&progress(3, 2000)), Some(0.0));
+    }
+
+    #[test]
+    fn test_json_lines_eta_ignores_finished_patterns() {
+        let reporter = JsonLinesReporter::new(Vec::new());
+        *reporter.targets.lock().unwrap() = vec![target("A", 0.01, 1), target("zzzz", 1e-6, 1)];
+
+        // The rare pattern dominates, and finding the common one does not shorten it
+        assert_eq!(reporter.eta_secs(&progress(0, 2000)), Some(1000.0));
+        let found = VanityResult {
+            public_key: "A1".to_string(),
+            key: crate::keygen::KeyMaterial::Keypair {
+                private_key: "secret".to_string().into(),
+            },
+            pattern: "A".to_string(),
+            attempts: 1,
+            time_elapsed: std::time::Duration::from_secs(1),
+        };
+        reporter.found(&found, &progress(1, 2000));
+        assert_eq!(reporter.targets.lock().unwrap()[0].remaining, 0);
+        assert_eq!(reporter.eta_secs(&progress(1, 2000)), Some(1000.0));
+    }
+
+    #[test]
+    fn test_json_lines_reporter_events() {
+        let buffer = SharedBuffer::default();
+        let generator = VanityGenerator::new().with_progress_reporter(Arc::new(JsonLinesReporter::new(buffer.clone())));
+        let options = VanityOptions::builder().pattern("A", 2).build().unwrap();
+        let outcome = generator.generate_multiple(options, 2).unwrap();
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let events: Vec<serde_json::Value> = output.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        let kinds: Vec<&str> = events.iter().map(|event| event["event"].as_str().unwrap()).collect();
+
+        assert_eq!(kinds.first(), Some(&"started"));
+        assert_eq!(kinds.last(), Some(&"finished"));
+        assert_eq!(kinds.iter().filter(|&&kind| kind == "found").count(), 2);
+        assert_eq!(events[0]["threads"], 2);
+        assert!(events[0]["expected_attempts"].as_u64().unwrap() > 1);
+
+        let found: Vec<&serde_json::Value> = events.iter().filter(|event| event["event"] == "found").collect();
+        assert_eq!(found[0]["public_key"], outcome.results[0].public_key.as_str());
+        assert_eq!(found[1]["found"], 2);
+        assert!(found.iter().all(|event| event.get("private_key").is_none()));
+        assert_eq!(events.last().unwrap()["reason"], "completed");
+        assert_eq!(events.last().unwrap()["total_attempts"], outcome.total_attempts);
+    }
+}
@@ -0,0 +1,127 @@
+use console::style;
+use std::time::Duration;
+
+use solana_vanity::output::{self, RedactedResult};
+use solana_vanity::{GenerationOutcome, SearchMode, StopReason, VanityGenerator, VanityOptions, VanityResult};
+
+use crate::benchmark;
+use crate::ProgressMode;
+
+/// Name the results that never reached `destination`, whose keys were only printed above
+pub fn report_unsaved(progress: ProgressMode, destination: &str, public_keys: &[String]) {
+    let message = format!("{} of the results were not saved to {}: {}", public_keys.len(), destination, public_keys.join(", "));
+    match progress {
+        ProgressMode::Bar => eprintln!("{}", style(format!("❌ {}", message)).red().bold()),
+        ProgressMode::Json => eprintln!("{}", serde_json::json!({ "event": "error", "message": message, "public_keys": public_keys })),
+    }
+}
+
+/// Print how hard the search is and how long it should take on this machine
+pub fn print_difficulty_estimate(generator: &VanityGenerator, options: &VanityOptions, thread_count: usize) -> anyhow::Result<()> {
+    let probability = generator.estimate_probability(options);
+    let expected_attempts = generator.estimate_expected_attempts(options);
+    let (thread_rate, cached) = benchmark::per_thread_rate(generator, options)?;
+    let speed = thread_rate * thread_count as f64;
+    let estimated_time = generator.estimate_expected_time(options, speed);
+    // The expected time is for one hit, so say so when more are wanted
+    let requested: usize = options.patterns.iter().map(|target| target.count).sum();
+    let per_address = if requested > 1 { format!(" (per address, of {})", requested) } else { String::new() };
+    let percentiles: Vec<String> = [("P50", 0.5), ("P90", 0.9), ("P99", 0.99)]
+        .iter()
+        .map(|&(label, quantile)| {
+            let time = generator.estimate_completion_quantile(options, speed, quantile);
+            format!("{} {}", label, generator.format_duration(time))
+        })
+        .collect();
+
+    println!("{}", style("Difficulty Estimate:").bold().yellow());
+    println!("  Probability: {}", style(format!("{:.6}%", probability * 100.0)).green());
+    println!("  Expected attempts: {}", style(expected_attempts.to_string()).green());
+    println!("  Speed: {}", style(format!("{:.0} attempts/sec ({:.0}/thread, {})",
+        speed, thread_rate, if cached { "cached" } else { "calibrated" })).green());
+    println!("  Estimated time{}: {}", per_address, style(generator.format_duration(estimated_time)).green());
+    println!("  Completion time (all {}): {}", requested, style(percentiles.join(", ")).green());
+    println!();
+    Ok(())
+}
+
+/// Print how the run ended
+pub fn print_summary(outcome: &GenerationOutcome, requested_count: usize, total_time: Duration) {
+    if outcome.stop_reason == StopReason::Completed {
+        println!("{}", style("✅ Generation Complete!").bold().green());
+    } else {
+        println!("{}", style(format!("⚠️  Stopped early: {} ({} of {} addresses found)",
+            outcome.stop_reason, outcome.results.len(), requested_count)).bold().yellow());
+    }
+    println!("  Stop reason: {}", style(outcome.stop_reason.to_string()).green());
+    println!("  Total time: {}", style(format!("{:.2}s", total_time.as_secs_f64())).green());
+    println!("  Total attempts: {}", style(outcome.total_attempts.to_string()).green());
+    println!("  Average speed: {}", style(format!("{:.0} attempts/sec", 
+        outcome.total_attempts as f64 / total_time.as_secs_f64())).green());
+    println!();
+}
+
+/// Report a problem that does not stop the search, in the style of the progress output
+pub fn warn(progress: ProgressMode, message: String) {
+    match progress {
+        ProgressMode::Bar => eprintln!("{}", style(format!("⚠️  {}", message)).red()),
+        ProgressMode::Json => eprintln!("{}", serde_json::json!({ "event": "warning", "message": message })),
+    }
+}
+
+/// Public keys of results that could not be written to each requested destination
+#[derive(Default)]
+pub struct UnsavedKeys {
+    pub output: Vec<String>,
+    pub keypair_dir: Vec<String>,
+}
+
+impl UnsavedKeys {
+    /// Whether a result is missing from some destination, so its key may exist nowhere else
+    pub fn contains(&self, public_key: &str) -> bool {
+        self.output.iter().chain(&self.keypair_dir).any(|unsaved| unsaved == public_key)
+    }
+}
+
+/// Print results for a terminal, leaving out the key material of results `redact` selects
+pub fn output_text(results: &[VanityResult], redact: impl Fn(&VanityResult) -> bool) {
+    for (i, result) in results.iter().enumerate() {
+        println!("{}", style(format!("Address #{}", i + 1)).bold().cyan());
+        println!("  Public Key:  {}", style(&result.public_key).green());
+        if !redact(result) {
+            for (label, value) in result.key.fields() {
+                println!("  {:<13}{}", format!("{}:", label), style(value).red());
+            }
+        }
+        println!("  Pattern:     {}", style(&result.pattern).cyan());
+        println!("  Time:        {}", style(format!("{:.2}s", result.time_elapsed.as_secs_f64())).yellow());
+        println!();
+    }
+}
+
+/// A result as printed: in full, or without its key material
+#[derive(serde::Serialize)]
+#[serde(untagged)]
+enum PrintedResult<'a> {
+    Full(&'a VanityResult),
+    Redacted(RedactedResult<'a>),
+}
+
+pub fn output_json(results: &[VanityResult], redact: impl Fn(&VanityResult) -> bool) -> anyhow::Result<()> {
+    let printed: Vec<PrintedResult> = results
+        .iter()
+        .map(|result| if redact(result) { PrintedResult::Redacted(result.into()) } else { PrintedResult::Full(result) })
+        .collect();
+    println!("{}", serde_json::to_string_pretty(&printed)?);
+    Ok(())
+}
+
+/// CSV rows share one header, so key columns are only dropped when every result is redacted
+pub fn output_csv(results: &[VanityResult], mode: &SearchMode, redact: impl Fn(&VanityResult) -> bool) -> anyhow::Result<()> {
+    let redact = results.iter().all(redact);
+    println!("{}", output::csv_header(mode, redact));
+    for result in results {
+        println!("{}", output::csv_row(result, redact));
+    }
+    Ok(())
+}
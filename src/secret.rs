@@ -0,0 +1,67 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use zeroize::Zeroize;
+
+/// A private key or seed phrase that is wiped from memory when dropped
+///
+/// Deliberately not `Clone`, so each secret has a single owner, and its `Debug`
+/// output never includes the value. The contents are only reachable through
+/// `expose`, for the few places that must write them out, and through `Serialize`.
+pub struct SecretString(String);
+
+impl SecretString {
+    pub fn new(secret: String) -> Self {
+        Self(secret)
+    }
+
+    /// The secret itself; only for writing it to its final destination
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        Self::new(secret)
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl std::fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SecretString(<redacted>)")
+    }
+}
+
+impl Serialize for SecretString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_hidden_from_debug_but_serialized() {
+        let secret = SecretString::new("hunter2".to_string());
+        assert!(!format!("{:?}", secret).contains("hunter2"));
+        assert_eq!(secret.expose(), "hunter2");
+
+        let json = serde_json::to_string(&secret).unwrap();
+        assert_eq!(json, "\"hunter2\"");
+        let parsed: SecretString = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.expose(), "hunter2");
+    }
+}
@@ -0,0 +1,444 @@
+use anyhow::Result;
+use axum::extract::rejection::JsonRejection;
+use axum::extract::{Path, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+use solana_vanity::{CancelHandle, PatternType, SearchEvent, SearchMode, StopReason, VanityGenerator, VanityOptions, VanityResult};
+
+/// A search to queue, as posted to `POST /jobs`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JobRequest {
+    pattern: String,
+    #[serde(default = "default_count")]
+    count: usize,
+    /// Same names as `--pattern-type`, e.g. "starts_with" or "regex"
+    #[serde(default)]
+    pattern_type: Option<String>,
+    #[serde(default)]
+    case_sensitive: bool,
+    #[serde(default)]
+    suffix: Option<String>,
+    #[serde(default)]
+    max_attempts: Option<u64>,
+    /// Seconds
+    #[serde(default)]
+    max_time: Option<u64>,
+    #[serde(default)]
+    mnemonic: bool,
+}
+
+fn default_count() -> usize {
+    1
+}
+
+impl JobRequest {
+    fn into_options(self) -> Result<VanityOptions, String> {
+        let mut builder = VanityOptions::builder()
+            .pattern(self.pattern, self.count)
+            .case_sensitive(self.case_sensitive);
+        if let Some(pattern_type) = &self.pattern_type {
+            builder = builder.pattern_type(pattern_type.parse::<PatternType>()?);
+        }
+        if let Some(suffix) = self.suffix {
+            builder = builder.suffix(suffix);
+        }
+        if let Some(max_attempts) = self.max_attempts {
+            builder = builder.max_attempts(max_attempts);
+        }
+        if let Some(max_time) = self.max_time {
+            builder = builder.max_time(Duration::from_secs(max_time));
+        }
+        if self.mnemonic {
+            builder = builder.mode(SearchMode::Mnemonic);
+        }
+        builder.build().map_err(|e| e.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum JobState {
+    Queued,
+    Running,
+    Finished,
+    Cancelled,
+    /// The search could not start; see `error`
+    Failed,
+}
+
+/// A queued, running or finished search and everything it found so far
+struct Job {
+    state: JobState,
+    patterns: Vec<String>,
+    requested: usize,
+    results: Vec<VanityResult>,
+    attempts: u64,
+    started_at: Option<Instant>,
+    /// Set once the job is done, so the reported speed stops changing
+    run_time: Option<Duration>,
+    stop_reason: Option<StopReason>,
+    error: Option<String>,
+    /// Stops the search directly, without waiting for its next event; set while running
+    cancel: Option<CancelHandle>,
+}
+
+impl Job {
+    fn new(options: &VanityOptions) -> Self {
+        Self {
+            state: JobState::Queued,
+            patterns: (0..options.patterns.len()).map(|index| options.pattern_label(index)).collect(),
+            requested: options.patterns.iter().map(|target| target.count).sum(),
+            results: Vec::new(),
+            attempts: 0,
+            started_at: None,
+            run_time: None,
+            stop_reason: None,
+            error: None,
+            cancel: None,
+        }
+    }
+
+    fn record(&mut self, event: SearchEvent) {
+        match event {
+            SearchEvent::Found(result) => self.results.push(result),
+            SearchEvent::Progress(progress) => self.attempts = progress.attempts,
+            SearchEvent::Finished { total_attempts, stop_reason } => {
+                self.attempts = total_attempts;
+                self.stop_reason = Some(stop_reason);
+                // Only a cancel request interrupts a job's search
+                self.state = if stop_reason == StopReason::Interrupted {
+                    JobState::Cancelled
+                } else {
+                    JobState::Finished
+                };
+                self.run_time = self.started_at.map(|started_at| started_at.elapsed());
+            }
+        }
+    }
+
+    fn status(&self, id: u64) -> JobStatus<'_> {
+        let elapsed = self
+            .run_time
+            .or_else(|| self.started_at.map(|started_at| started_at.elapsed()))
+            .unwrap_or_default();
+        let attempts_per_second = if elapsed.is_zero() {
+            0.0
+        } else {
+            self.attempts as f64 / elapsed.as_secs_f64()
+        };
+
+        JobStatus {
+            id,
+            state: self.state,
+            patterns: &self.patterns,
+            requested: self.requested,
+            found: self.results.len(),
+            attempts: self.attempts,
+            attempts_per_second,
+            elapsed_secs: elapsed.as_secs_f64(),
+            stop_reason: self.stop_reason,
+            error: self.error.as_deref(),
+        }
+    }
+}
+
+/// What `GET /jobs/:id` returns; never includes key material
+#[derive(Serialize)]
+struct JobStatus<'a> {
+    id: u64,
+    state: JobState,
+    patterns: &'a [String],
+    requested: usize,
+    found: usize,
+    attempts: u64,
+    attempts_per_second: f64,
+    elapsed_secs: f64,
+    stop_reason: Option<StopReason>,
+    error: Option<&'a str>,
+}
+
+type Jobs = Arc<Mutex<BTreeMap<u64, Job>>>;
+
+/// Shared by every request handler
+#[derive(Clone)]
+pub struct AppState {
+    jobs: Jobs,
+    next_id: Arc<AtomicU64>,
+    queue: mpsc::UnboundedSender<(u64, VanityOptions)>,
+}
+
+/// Start the job runner and return the state the routes share
+///
+/// Jobs run one at a time, each on `thread_count` threads, in the order they were posted.
+pub fn start(thread_count: usize) -> AppState {
+    let jobs = Jobs::default();
+    let (queue, queued) = mpsc::unbounded_channel();
+    tokio::spawn(run_jobs(Arc::clone(&jobs), queued, thread_count));
+
+    AppState {
+        jobs,
+        next_id: Arc::new(AtomicU64::new(1)),
+        queue,
+    }
+}
+
+pub fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/jobs", post(create_job).get(list_jobs))
+        .route("/jobs/:id", get(get_job).delete(delete_job))
+        .route("/jobs/:id/results", get(get_results))
+        .route("/jobs/:id/cancel", post(cancel_job))
+        .with_state(state)
+}
+
+/// Serve the job API on `listen` until the process is stopped
+pub async fn serve(listen: SocketAddr, thread_count: usize) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(listen).await?;
+    println!("Listening on http://{}", listener.local_addr()?);
+    axum::serve(listener, router(start(thread_count))).await?;
+    Ok(())
+}
+
+/// Take jobs off the queue and search for each in turn, recording every event
+async fn run_jobs(jobs: Jobs, mut queued: mpsc::UnboundedReceiver<(u64, VanityOptions)>, thread_count: usize) {
+    let generator = VanityGenerator::new();
+
+    while let Some((id, options)) = queued.recv().await {
+        // Start the search under the lock, so a cancel request always finds its handle
+        let mut stream = {
+            let mut guard = jobs.lock().unwrap();
+            // Jobs cancelled or deleted while queued are skipped
+            let Some(job) = guard.get_mut(&id).filter(|job| job.state == JobState::Queued) else {
+                continue;
+            };
+            match generator.generate_stream(options, thread_count) {
+                Ok(stream) => {
+                    job.state = JobState::Running;
+                    job.started_at = Some(Instant::now());
+                    job.cancel = Some(stream.cancel_handle());
+                    stream
+                }
+                Err(e) => {
+                    job.state = JobState::Failed;
+                    job.error = Some(e.to_string());
+                    continue;
+                }
+            }
+        };
+
+        while let Some(event) = stream.recv().await {
+            if let Some(job) = jobs.lock().unwrap().get_mut(&id) {
+                job.record(event);
+            }
+        }
+    }
+}
+
+/// An error response with a JSON `{"error": ...}` body
+struct ApiError(StatusCode, String);
+
+impl ApiError {
+    fn not_found(id: u64) -> Self {
+        Self(StatusCode::NOT_FOUND, format!("No job {}", id))
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(serde_json::json!({ "error": self.1 }))).into_response()
+    }
+}
+
+async fn create_job(
+    State(state): State<AppState>,
+    request: Result<Json<JobRequest>, JsonRejection>,
+) -> Result<Response, ApiError> {
+    let Json(request) = request.map_err(|e| ApiError(StatusCode::BAD_REQUEST, e.body_text()))?;
+    let options = request
+        .into_options()
+        .map_err(|message| ApiError(StatusCode::BAD_REQUEST, message))?;
+
+    let id = state.next_id.fetch_add(1, Ordering::Relaxed);
+    let mut jobs = state.jobs.lock().unwrap();
+    let job = jobs.entry(id).or_insert(Job::new(&options));
+    state
+        .queue
+        .send((id, options))
+        .map_err(|_| ApiError(StatusCode::SERVICE_UNAVAILABLE, "Job runner has stopped".to_string()))?;
+
+    Ok((StatusCode::CREATED, Json(job.status(id))).into_response())
+}
+
+async fn list_jobs(State(state): State<AppState>) -> Response {
+    let jobs = state.jobs.lock().unwrap();
+    let statuses: Vec<JobStatus> = jobs.iter().map(|(&id, job)| job.status(id)).collect();
+    Json(statuses).into_response()
+}
+
+async fn get_job(State(state): State<AppState>, Path(id): Path<u64>) -> Result<Response, ApiError> {
+    let jobs = state.jobs.lock().unwrap();
+    let job = jobs.get(&id).ok_or(ApiError::not_found(id))?;
+    Ok(Json(job.status(id)).into_response())
+}
+
+/// Every address found so far, with its key material, in the same shape as `--format json`
+async fn get_results(State(state): State<AppState>, Path(id): Path<u64>) -> Result<Response, ApiError> {
+    let jobs = state.jobs.lock().unwrap();
+    let job = jobs.get(&id).ok_or(ApiError::not_found(id))?;
+    let body = serde_json::to_vec_pretty(&job.results)
+        .map_err(|e| ApiError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(([(header::CONTENT_TYPE, "application/json")], body).into_response())
+}
+
+/// Stop a job; results found before it stopped are kept
+async fn cancel_job(State(state): State<AppState>, Path(id): Path<u64>) -> Result<Response, ApiError> {
+    let mut jobs = state.jobs.lock().unwrap();
+    let job = jobs.get_mut(&id).ok_or(ApiError::not_found(id))?;
+    match (job.state, &job.cancel) {
+        (JobState::Queued, _) => job.state = JobState::Cancelled,
+        (JobState::Running, Some(cancel)) => cancel.cancel(),
+        _ => {}
+    }
+    Ok(Json(job.status(id)).into_response())
+}
+
+/// Forget a job and wipe its results, stopping it first if it is still running
+async fn delete_job(State(state): State<AppState>, Path(id): Path<u64>) -> Result<StatusCode, ApiError> {
+    let job = state.jobs.lock().unwrap().remove(&id).ok_or(ApiError::not_found(id))?;
+    if let Some(cancel) = &job.cancel {
+        cancel.cancel();
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    async fn send(app: &Router, method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(body.map_or_else(Body::empty, |body| Body::from(body.to_string())))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+    }
+
+    /// Poll a job until it reaches `state`, failing after a minute
+    async fn wait_for(app: &Router, id: u64, state: &str) -> Value {
+        for _ in 0..600 {
+            let (_, status) = send(app, "GET", &format!("/jobs/{}", id), None).await;
+            if status["state"] == state {
+                return status;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        panic!("job {} never reached {}", id, state);
+    }
+
+    #[tokio::test]
+    async fn test_job_lifecycle() {
+        let app = router(start(2));
+
+        let (status, job) = send(&app, "POST", "/jobs", Some(json!({ "pattern": "A", "count": 2 }))).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(job["requested"], 2);
+        let id = job["id"].as_u64().unwrap();
+
+        let job = wait_for(&app, id, "finished").await;
+        assert_eq!(job["found"], 2);
+        assert_eq!(job["stop_reason"], "completed");
+        assert!(job.get("results").is_none());
+
+        let (status, results) = send(&app, "GET", &format!("/jobs/{}/results", id), None).await;
+        assert_eq!(status, StatusCode::OK);
+        let results = results.as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        for result in results {
+            assert!(result["public_key"].as_str().unwrap().to_lowercase().starts_with('a'));
+            assert!(result["private_key"].is_string());
+        }
+
+        let (status, _) = send(&app, "DELETE", &format!("/jobs/{}", id), None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, error) = send(&app, "GET", &format!("/jobs/{}", id), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(error["error"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_jobs_queue_and_cancel() {
+        let app = router(start(2));
+
+        let hopeless = json!({ "pattern": "zzzzzzzz", "case_sensitive": true, "max_attempts": u64::MAX });
+        let (_, first) = send(&app, "POST", "/jobs", Some(hopeless)).await;
+        let (_, second) = send(&app, "POST", "/jobs", Some(json!({ "pattern": "B" }))).await;
+        let (first, second) = (first["id"].as_u64().unwrap(), second["id"].as_u64().unwrap());
+
+        // Only one job runs at a time
+        wait_for(&app, first, "running").await;
+        let (_, status) = send(&app, "GET", &format!("/jobs/{}", second), None).await;
+        assert_eq!(status["state"], "queued");
+
+        let (status, _) = send(&app, "POST", &format!("/jobs/{}/cancel", first), None).await;
+        assert_eq!(status, StatusCode::OK);
+        let cancelled = wait_for(&app, first, "cancelled").await;
+        assert_eq!(cancelled["stop_reason"], "interrupted");
+        wait_for(&app, second, "finished").await;
+
+        let (_, jobs) = send(&app, "GET", "/jobs", None).await;
+        assert_eq!(jobs.as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_stops_slow_searches_promptly() {
+        let app = router(start(2));
+
+        // Each mnemonic attempt runs PBKDF2, so a batch of attempts takes minutes in debug builds
+        let hopeless = json!({ "pattern": "zzzzzzzz", "case_sensitive": true, "max_attempts": u64::MAX, "mnemonic": true });
+        let (_, job) = send(&app, "POST", "/jobs", Some(hopeless)).await;
+        let id = job["id"].as_u64().unwrap();
+        wait_for(&app, id, "running").await;
+
+        let start = Instant::now();
+        send(&app, "POST", &format!("/jobs/{}/cancel", id), None).await;
+        wait_for(&app, id, "cancelled").await;
+        assert!(start.elapsed() < Duration::from_secs(10), "cancel took {:?}", start.elapsed());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_jobs_rejected() {
+        let app = router(start(1));
+
+        let (status, error) = send(&app, "POST", "/jobs", Some(json!({ "pattern": "SOL" }))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(error["error"].as_str().unwrap().contains("SOL"));
+
+        let (status, _) = send(&app, "POST", "/jobs", Some(json!({ "pattern": "A", "pattern_type": "fuzzy" }))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = send(&app, "POST", "/jobs", Some(json!({ "patern": "A" }))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = send(&app, "POST", "/jobs/7/cancel", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
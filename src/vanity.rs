@@ -1,16 +1,22 @@
-use anyhow::Result;
-use bs58;
 use serde::{Deserialize, Serialize};
-use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::pubkey::Pubkey;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use crate::error::{Result, VanityError};
+use crate::keygen::{KeyMaterial, SearchMode};
+use crate::pattern::{self, PatternMatcher};
+use crate::prefilter;
+use crate::progress::ProgressReporter;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PatternType {
     StartsWith,
     EndsWith,
     Contains,
+    Regex,
+    Glob,
 }
 
 impl std::str::FromStr for PatternType {
@@ -21,190 +27,633 @@ impl std::str::FromStr for PatternType {
             "starts_with" | "starts" | "start" => Ok(PatternType::StartsWith),
             "ends_with" | "ends" | "end" => Ok(PatternType::EndsWith),
             "contains" | "contain" => Ok(PatternType::Contains),
+            "regex" | "re" => Ok(PatternType::Regex),
+            "glob" => Ok(PatternType::Glob),
             _ => Err(format!("Invalid pattern type: {}", s)),
         }
     }
 }
 
+/// A pattern together with how many addresses it should produce
 #[derive(Debug, Clone)]
-pub struct VanityOptions {
+pub struct PatternTarget {
     pub pattern: String,
+    pub count: usize,
+}
+
+impl PatternTarget {
+    pub fn new(pattern: &str, count: usize) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            count,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VanityOptions {
+    pub patterns: Vec<PatternTarget>,
     pub pattern_type: PatternType,
     pub case_sensitive: bool,
-    #[allow(dead_code)]
+    /// Suffix the key must also end with, on top of matching one of the patterns
+    pub suffix: Option<String>,
     pub max_attempts: u64,
     pub max_time: Duration,
+    /// How candidate addresses are generated
+    pub mode: SearchMode,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl VanityOptions {
+    /// Start building options; unset fields take the same defaults as the CLI
+    pub fn builder() -> VanityOptionsBuilder {
+        VanityOptionsBuilder::default()
+    }
+
+    /// Human-readable label for the pattern at `index`, including any required suffix
+    pub fn pattern_label(&self, index: usize) -> String {
+        match &self.suffix {
+            Some(suffix) => format!("{}...{}", self.patterns[index].pattern, suffix),
+            None => self.patterns[index].pattern.clone(),
+        }
+    }
+}
+
+/// Builds `VanityOptions`, checking every pattern and the search mode up front
+///
+/// ```
+/// use solana_vanity::{PatternType, VanityOptions};
+///
+/// let options = VanityOptions::builder()
+///     .pattern("ABC", 2)
+///     .pattern_type(PatternType::StartsWith)
+///     .suffix("z")
+///     .build()
+///     .unwrap();
+/// assert_eq!(options.pattern_label(0), "ABC...z");
+/// ```
+#[derive(Debug, Clone)]
+pub struct VanityOptionsBuilder {
+    options: VanityOptions,
+}
+
+impl Default for VanityOptionsBuilder {
+    fn default() -> Self {
+        Self {
+            options: VanityOptions {
+                patterns: Vec::new(),
+                pattern_type: PatternType::StartsWith,
+                case_sensitive: false,
+                suffix: None,
+                max_attempts: 10_000_000,
+                max_time: Duration::from_secs(300),
+                mode: SearchMode::Keypair,
+            },
+        }
+    }
+}
+
+impl VanityOptionsBuilder {
+    /// Search for `pattern` until `count` addresses match it; call once per pattern
+    pub fn pattern(mut self, pattern: impl Into<String>, count: usize) -> Self {
+        self.options.patterns.push(PatternTarget {
+            pattern: pattern.into(),
+            count,
+        });
+        self
+    }
+
+    pub fn pattern_type(mut self, pattern_type: PatternType) -> Self {
+        self.options.pattern_type = pattern_type;
+        self
+    }
+
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.options.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Also require every address to end with `suffix`; only valid with `PatternType::StartsWith`
+    pub fn suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.options.suffix = Some(suffix.into());
+        self
+    }
+
+    pub fn max_attempts(mut self, max_attempts: u64) -> Self {
+        self.options.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn max_time(mut self, max_time: Duration) -> Self {
+        self.options.max_time = max_time;
+        self
+    }
+
+    pub fn mode(mut self, mode: SearchMode) -> Self {
+        self.options.mode = mode;
+        self
+    }
+
+    /// Validate the patterns, suffix and search mode and return the finished options
+    pub fn build(self) -> Result<VanityOptions> {
+        let options = self.options;
+        if options.patterns.is_empty() {
+            return Err(VanityError::NoPatterns);
+        }
+        for target in &options.patterns {
+            validate_pattern(&target.pattern, &options.pattern_type, options.case_sensitive)?;
+            if target.count == 0 {
+                return Err(VanityError::InvalidPattern {
+                    pattern: target.pattern.clone(),
+                    message: "count must be at least 1".to_string(),
+                });
+            }
+        }
+        if let Some(suffix) = &options.suffix {
+            // Only a prefix is known not to overlap the suffix, which the estimates rely on
+            if !matches!(options.pattern_type, PatternType::StartsWith) {
+                return Err(VanityError::InvalidOptions(
+                    "A suffix can only be combined with starts_with patterns".to_string(),
+                ));
+            }
+            validate_pattern(suffix, &PatternType::EndsWith, options.case_sensitive)?;
+        }
+        options.mode.validate()?;
+        Ok(options)
+    }
+}
+
+/// A found address and what is needed to use it
+///
+/// Not `Clone`: each result owns its key material, which is wiped when it is dropped.
+#[derive(Debug, Serialize, Deserialize)]
 pub struct VanityResult {
     pub public_key: String,
-    pub private_key: String,
+    #[serde(flatten)]
+    pub key: KeyMaterial,
+    /// The pattern this address satisfied
+    pub pattern: String,
     pub attempts: u64,
     pub time_elapsed: Duration,
 }
 
+/// Why a search run ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    /// Every pattern reached its requested count
+    Completed,
+    TimeLimit,
+    AttemptLimit,
+    /// Stopped through `VanityGenerator::cancel` (e.g. on Ctrl-C) or `ResultStream::cancel`
+    Interrupted,
+}
+
+impl std::fmt::Display for StopReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StopReason::Completed => write!(f, "all addresses found"),
+            StopReason::TimeLimit => write!(f, "time limit reached"),
+            StopReason::AttemptLimit => write!(f, "attempt limit reached"),
+            StopReason::Interrupted => write!(f, "interrupted"),
+        }
+    }
+}
+
+/// Everything a search run produced
+#[derive(Debug)]
+pub struct GenerationOutcome {
+    pub results: Vec<VanityResult>,
+    pub total_attempts: u64,
+    pub stop_reason: StopReason,
+}
+
+/// Number of attempts a worker reserves from the shared budget at a time
+const ATTEMPT_BATCH: u64 = 1000;
+
+/// How often a running search reports its progress
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Tally shared between workers: addresses found per pattern and why the search stopped
+struct SearchState {
+    found: Vec<usize>,
+    found_total: usize,
+    stop_reason: Option<StopReason>,
+}
+
+impl SearchState {
+    fn new(pattern_count: usize) -> Self {
+        Self {
+            found: vec![0; pattern_count],
+            found_total: 0,
+            stop_reason: None,
+        }
+    }
+
+    /// Record why the search is stopping; the first reason wins
+    fn stop(&mut self, reason: StopReason, stop_flag: &AtomicBool) {
+        self.stop_reason.get_or_insert(reason);
+        stop_flag.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Snapshot of a running search
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SearchProgress {
+    /// Keys tried so far across all workers
+    pub attempts: u64,
+    /// Addresses found so far
+    pub found: usize,
+    /// Addresses requested across all patterns
+    pub requested: usize,
+    pub elapsed: Duration,
+}
+
+impl SearchProgress {
+    pub fn attempts_per_second(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 {
+            self.attempts as f64 / seconds
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Something that happened during a search
+#[derive(Debug)]
+pub enum SearchEvent {
+    /// A matching address, delivered the moment a worker finds it
+    Found(VanityResult),
+    /// Sent a few times a second while the search runs
+    Progress(SearchProgress),
+    /// Always the last event of a stream
+    Finished {
+        total_attempts: u64,
+        stop_reason: StopReason,
+    },
+}
+
+/// Events of a search running in the background, from `VanityGenerator::generate_stream`
+///
+/// Dropping the stream cancels the search.
+pub struct ResultStream {
+    events: tokio::sync::mpsc::UnboundedReceiver<SearchEvent>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl ResultStream {
+    /// The next event, or `None` once `Finished` has been received
+    pub async fn recv(&mut self) -> Option<SearchEvent> {
+        self.events.recv().await
+    }
+
+    /// Blocking version of `recv` for synchronous callers; panics inside an async runtime
+    pub fn blocking_recv(&mut self) -> Option<SearchEvent> {
+        self.events.blocking_recv()
+    }
+
+    /// Stop this search only; workers drain and a `Finished` event with
+    /// `StopReason::Interrupted` follows any results already sent
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// A handle that cancels this search from elsewhere, e.g. another task, while this one waits in `recv`
+    pub fn cancel_handle(&self) -> CancelHandle {
+        CancelHandle(Arc::clone(&self.cancelled))
+    }
+}
+
+/// Cancels the search of one `ResultStream`; see `ResultStream::cancel_handle`
+#[derive(Debug, Clone)]
+pub struct CancelHandle(Arc<AtomicBool>);
+
+impl CancelHandle {
+    /// Same as `ResultStream::cancel`; workers notice it before their next attempt
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Drop for ResultStream {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// Callback invoked with every result the moment a worker finds it
+pub type ResultHandler = Arc<dyn Fn(&VanityResult) + Send + Sync>;
+
+#[derive(Clone, Default)]
 pub struct VanityGenerator {
-    // No state needed - all operations are stateless
+    /// Pin each worker thread to its own CPU core
+    pin_cores: bool,
+    result_handler: Option<ResultHandler>,
+    /// Told about every search this generator runs; none means silent
+    progress_reporter: Option<Arc<dyn ProgressReporter>>,
+    /// Shared by every clone, so any of them can stop a run in progress
+    cancelled: Arc<AtomicBool>,
 }
 
 impl VanityGenerator {
     pub fn new() -> Self {
-        Self {}
+        Self::default()
+    }
+
+    /// Pin worker threads to CPU cores, which helps on machines dedicated to grinding
+    pub fn with_core_pinning(mut self, pin_cores: bool) -> Self {
+        self.pin_cores = pin_cores;
+        self
+    }
+
+    /// Receive each result as soon as it is found, e.g. to persist it immediately
+    ///
+    /// The handler runs before the next result is accepted, so results arrive in order.
+    pub fn with_result_handler(mut self, handler: ResultHandler) -> Self {
+        self.result_handler = Some(handler);
+        self
+    }
+
+    /// Report progress of every search, e.g. with a progress bar or as JSON lines
+    ///
+    /// Without a reporter, searches run silently.
+    pub fn with_progress_reporter(mut self, reporter: Arc<dyn ProgressReporter>) -> Self {
+        self.progress_reporter = Some(reporter);
+        self
     }
 
-    /// Generate a single vanity address
-    #[allow(dead_code)]
-    pub async fn generate_single(&self, options: &VanityOptions) -> Result<Option<VanityResult>> {
+    /// Ask any run in progress to stop; workers drain and the results found so far are returned
+    ///
+    /// Cancellation is sticky: later runs on this generator (or its clones) stop immediately.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Generate a single vanity address on the current thread
+    pub fn generate_single(&self, options: &VanityOptions) -> Result<Option<VanityResult>> {
+        options.mode.validate()?;
+        let matcher = PatternMatcher::new(options)?;
         let start_time = Instant::now();
         let mut attempts = 0u64;
 
         while attempts < options.max_attempts && start_time.elapsed() < options.max_time {
+            // Generate a new candidate address
+            let candidate = options.mode.generate(attempts);
             attempts += 1;
 
-            // Generate a new keypair
-            let keypair = Keypair::new();
-            let public_key = keypair.pubkey().to_string();
-
             // Check if it matches our criteria
-            if self.matches_pattern(&public_key, &options.pattern, &options.pattern_type, options.case_sensitive) {
+            if let Some((public_key, matched)) = Self::match_candidate(&matcher, &candidate.pubkey()) {
+                let index = matched[0];
                 return Ok(Some(VanityResult {
                     public_key,
-                    private_key: bs58::encode(&keypair.to_bytes()).into_string(),
+                    key: candidate.into_key_material(),
+                    pattern: options.pattern_label(index),
                     attempts,
                     time_elapsed: start_time.elapsed(),
                 }));
             }
-
-            // Yield control periodically to prevent blocking
-            if attempts % 10000 == 0 {
-                tokio::task::yield_now().await;
-            }
         }
 
         Ok(None)
     }
 
-    /// Generate multiple addresses in parallel
+    /// Generate addresses in parallel without blocking the async runtime
+    ///
+    /// The search itself runs on a dedicated thread pool; see `generate_multiple`.
     pub async fn generate_multiple_parallel(
         &self,
-        count: usize,
         options: VanityOptions,
         thread_count: usize,
-    ) -> Result<(Vec<VanityResult>, u64)> {
-        let results = Arc::new(std::sync::Mutex::new(Vec::new()));
-        let stop_flag = Arc::new(AtomicBool::new(false));
-        let total_attempts = Arc::new(AtomicU64::new(0));
-
-        // Create a progress bar
-        let pb = indicatif::ProgressBar::new(count as u64);
-        pb.set_style(
-            indicatif::ProgressStyle::default_bar()
-                .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} addresses ({percent}%) {msg}")
-                .unwrap()
-                .progress_chars("#>-"),
-        );
-
-        // Spawn worker threads
-        let handles: Vec<_> = (0..thread_count)
-            .map(|_| {
-                let options = options.clone();
-                let results = Arc::clone(&results);
-                let stop_flag = Arc::clone(&stop_flag);
-                let total_attempts = Arc::clone(&total_attempts);
-                let pb = pb.clone();
-
-                tokio::spawn(async move {
-                    let mut local_attempts = 0u64;
-                    let start_time = Instant::now();
-
-                    loop {
-                        // Check if we should stop
-                        if stop_flag.load(Ordering::Relaxed) {
-                            break;
-                        }
+    ) -> Result<GenerationOutcome> {
+        let generator = self.clone();
+        tokio::task::spawn_blocking(move || generator.generate_multiple(options, thread_count)).await?
+    }
 
-                        // Check time limit
-                        if start_time.elapsed() > options.max_time {
-                            break;
-                        }
+    /// Generate addresses on a dedicated CPU thread pool until every pattern has reached its count
+    ///
+    /// Stops early once `max_time` elapses or `max_attempts` keys have been tried across all
+    /// workers. Blocks the calling thread, so it can be used without an async runtime.
+    pub fn generate_multiple(
+        &self,
+        options: VanityOptions,
+        thread_count: usize,
+    ) -> Result<GenerationOutcome> {
+        options.mode.validate()?;
+        let matcher = PatternMatcher::new(&options)?;
+        let pool = self.build_thread_pool(thread_count)?;
+        let results = std::sync::Mutex::new(Vec::new());
 
-                        // Check if we have enough results
-                        {
-                            let results_guard = results.lock().unwrap();
-                            if results_guard.len() >= count {
-                                break;
-                            }
-                        }
+        let (total_attempts, stop_reason) = self.search(&options, &matcher, &pool, &AtomicBool::new(false), &|event| {
+            if let SearchEvent::Found(result) = event {
+                results.lock().unwrap().push(result);
+            }
+        });
+
+        Ok(GenerationOutcome {
+            results: results.into_inner().unwrap(),
+            total_attempts,
+            stop_reason,
+        })
+    }
+
+    /// Start a search in the background and receive its results and progress as they happen
+    ///
+    /// Options are validated and the thread pool is built before returning, so the stream
+    /// only carries events of a search that actually started. Unlike `cancel`, cancelling
+    /// or dropping the stream stops only this search.
+    pub fn generate_stream(&self, options: VanityOptions, thread_count: usize) -> Result<ResultStream> {
+        options.mode.validate()?;
+        let matcher = PatternMatcher::new(&options)?;
+        let pool = self.build_thread_pool(thread_count)?;
+        let (sender, events) = tokio::sync::mpsc::unbounded_channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        let generator = self.clone();
+        let stream_cancelled = Arc::clone(&cancelled);
+        std::thread::spawn(move || {
+            let (total_attempts, stop_reason) = generator.search(&options, &matcher, &pool, &stream_cancelled, &|event| {
+                // A closed channel means the stream was dropped, which also cancels the search
+                let _ = sender.send(event);
+            });
+            let _ = sender.send(SearchEvent::Finished {
+                total_attempts,
+                stop_reason,
+            });
+        });
+
+        Ok(ResultStream { events, cancelled })
+    }
+
+    /// Run the workers until the search is done, passing every result and progress update to `on_event`
+    ///
+    /// `on_event` is called under the results lock for `Found`, so results arrive in order.
+    /// The progress reporter sees the same events. Returns the attempts made and why the
+    /// search stopped.
+    fn search(
+        &self,
+        options: &VanityOptions,
+        matcher: &PatternMatcher,
+        pool: &rayon::ThreadPool,
+        cancelled: &AtomicBool,
+        on_event: &(dyn Fn(SearchEvent) + Sync),
+    ) -> (u64, StopReason) {
+        let search = std::sync::Mutex::new(SearchState::new(options.patterns.len()));
+        let stop_flag = AtomicBool::new(false);
+        let reserved_attempts = AtomicU64::new(0);
+        let total_attempts = AtomicU64::new(0);
+        let last_progress_ms = AtomicU64::new(0);
+        let total_count: usize = options.patterns.iter().map(|target| target.count).sum();
+        let reporter = self.progress_reporter.as_deref();
+        if let Some(reporter) = reporter {
+            reporter.started(options, pool.current_num_threads());
+        }
+        let start_time = Instant::now();
+
+        // Run one worker on every thread of the pool and wait for them all to finish
+        pool.broadcast(|_| {
+            while !stop_flag.load(Ordering::Relaxed) {
+                // Reserve a batch of attempts from the shared budget; once it is spent,
+                // workers finish their batches and exit on their own
+                let batch_start = reserved_attempts.fetch_add(ATTEMPT_BATCH, Ordering::Relaxed);
+                if batch_start >= options.max_attempts {
+                    break;
+                }
+                let batch_size = ATTEMPT_BATCH.min(options.max_attempts - batch_start);
+                let mut used = 0u64;
+
+                while used < batch_size {
+                    // Check if we should stop (set once every pattern has enough results)
+                    if stop_flag.load(Ordering::Relaxed) {
+                        break;
+                    }
 
-                        local_attempts += 1;
+                    // Check for cancellation, of this search or of the whole generator
+                    if cancelled.load(Ordering::Relaxed) || self.cancelled.load(Ordering::Relaxed) {
+                        search.lock().unwrap().stop(StopReason::Interrupted, &stop_flag);
+                        break;
+                    }
+
+                    // Check time limit
+                    if start_time.elapsed() > options.max_time {
+                        search.lock().unwrap().stop(StopReason::TimeLimit, &stop_flag);
+                        break;
+                    }
 
-                        // Generate a new keypair
-                        let keypair = Keypair::new();
-                        let public_key = keypair.pubkey().to_string();
+                    // Generate a new candidate address
+                    let candidate = options.mode.generate(batch_start + used);
+                    used += 1;
 
-                        // Check if it matches our criteria
-                        if Self::matches_pattern_static(&public_key, &options.pattern, &options.pattern_type, options.case_sensitive) {
+                    // Check if it matches any of our patterns
+                    if let Some((public_key, matched)) = Self::match_candidate(matcher, &candidate.pubkey()) {
+                        let mut search_guard = search.lock().unwrap();
+
+                        // A key can only be used once, so credit the first pattern still short of its count
+                        if let Some(index) = matched.into_iter().find(|&i| search_guard.found[i] < options.patterns[i].count) {
                             let result = VanityResult {
                                 public_key,
-                                private_key: bs58::encode(&keypair.to_bytes()).into_string(),
-                                attempts: local_attempts,
+                                key: candidate.into_key_material(),
+                                pattern: options.pattern_label(index),
+                                attempts: total_attempts.load(Ordering::Relaxed) + used,
                                 time_elapsed: start_time.elapsed(),
                             };
+                            if let Some(handler) = &self.result_handler {
+                                handler(&result);
+                            }
 
-                            // Add to results
-                            {
-                                let mut results_guard = results.lock().unwrap();
-                                if results_guard.len() < count {
-                                    results_guard.push(result);
-                                    pb.inc(1);
-                                    
-                                    if results_guard.len() >= count {
-                                        stop_flag.store(true, Ordering::Relaxed);
-                                    }
-                                }
+                            search_guard.found[index] += 1;
+                            search_guard.found_total += 1;
+                            if let Some(reporter) = reporter {
+                                reporter.found(&result, &SearchProgress {
+                                    attempts: result.attempts,
+                                    found: search_guard.found_total,
+                                    requested: total_count,
+                                    elapsed: result.time_elapsed,
+                                });
                             }
-                        }
+                            on_event(SearchEvent::Found(result));
 
-                        // Update total attempts periodically
-                        if local_attempts % 1000 == 0 {
-                            total_attempts.fetch_add(1000, Ordering::Relaxed);
-                            local_attempts = 0;
-                            
-                            // Update progress message
-                            let current_attempts = total_attempts.load(Ordering::Relaxed);
-                            let elapsed = start_time.elapsed();
-                            let speed = current_attempts as f64 / elapsed.as_secs_f64();
-                            pb.set_message(format!("{:.0} attempts/sec", speed));
+                            if search_guard.found_total >= total_count {
+                                search_guard.stop(StopReason::Completed, &stop_flag);
+                            }
                         }
                     }
+                }
 
-                    // Add remaining attempts
-                    total_attempts.fetch_add(local_attempts, Ordering::Relaxed);
-                })
-            })
-            .collect();
+                // Update total attempts
+                let current_attempts = total_attempts.fetch_add(used, Ordering::Relaxed) + used;
 
-        // Wait for all threads to complete
-        for handle in handles {
-            handle.await?;
+                // Report progress, from whichever worker first notices the interval has passed
+                let elapsed = start_time.elapsed();
+                let elapsed_ms = elapsed.as_millis() as u64;
+                let last_ms = last_progress_ms.load(Ordering::Relaxed);
+                if elapsed_ms >= last_ms + PROGRESS_INTERVAL.as_millis() as u64
+                    && last_progress_ms
+                        .compare_exchange(last_ms, elapsed_ms, Ordering::Relaxed, Ordering::Relaxed)
+                        .is_ok()
+                {
+                    let progress = SearchProgress {
+                        attempts: current_attempts,
+                        found: search.lock().unwrap().found_total,
+                        requested: total_count,
+                        elapsed,
+                    };
+                    if let Some(reporter) = reporter {
+                        reporter.progress(&progress);
+                    }
+                    on_event(SearchEvent::Progress(progress));
+                }
+            }
+        });
+
+        let search = search.into_inner().unwrap();
+        let total_attempts = total_attempts.load(Ordering::Relaxed);
+        // Workers only exit without a recorded reason once the attempt budget is spent
+        let stop_reason = search.stop_reason.unwrap_or(StopReason::AttemptLimit);
+        if let Some(reporter) = reporter {
+            reporter.finished(total_attempts, stop_reason);
         }
+        (total_attempts, stop_reason)
+    }
 
-        pb.finish_with_message("Generation complete!");
+    /// Build the worker pool, optionally pinning each thread to a core
+    fn build_thread_pool(&self, thread_count: usize) -> Result<rayon::ThreadPool> {
+        let mut builder = rayon::ThreadPoolBuilder::new()
+            .num_threads(thread_count.max(1))
+            .thread_name(|index| format!("vanity-worker-{}", index));
 
-        let final_results = results.lock().unwrap().clone();
-        let final_total_attempts = total_attempts.load(Ordering::Relaxed);
-        Ok((final_results, final_total_attempts))
+        if self.pin_cores {
+            if let Some(core_ids) = core_affinity::get_core_ids().filter(|ids| !ids.is_empty()) {
+                builder = builder.start_handler(move |index| {
+                    core_affinity::set_for_current(core_ids[index % core_ids.len()]);
+                });
+            }
+        }
+
+        Ok(builder.build()?)
     }
 
-    /// Check if a public key matches the specified pattern
-    #[allow(dead_code)]
-    fn matches_pattern(&self, public_key: &str, pattern: &str, pattern_type: &PatternType, case_sensitive: bool) -> bool {
-        Self::matches_pattern_static(public_key, pattern, pattern_type, case_sensitive)
+    /// Match a candidate key, returning its encoding and the patterns it satisfies
+    ///
+    /// Raw key bytes are checked against the matcher's pre-filter first, so only
+    /// plausible candidates pay for Base58 encoding.
+    fn match_candidate(matcher: &PatternMatcher, pubkey: &Pubkey) -> Option<(String, Vec<usize>)> {
+        if !matcher.may_match(&pubkey.to_bytes()) {
+            return None;
+        }
+
+        let public_key = pubkey.to_string();
+        let matched = matcher.matching_patterns(&public_key);
+        if matched.is_empty() {
+            None
+        } else {
+            Some((public_key, matched))
+        }
     }
 
-    /// Static version for use in parallel contexts
-    fn matches_pattern_static(public_key: &str, pattern: &str, pattern_type: &PatternType, case_sensitive: bool) -> bool {
+    /// Check if a public key matches a single pattern
+    ///
+    /// Regex and glob patterns are compiled on every call here; hot loops should use a `PatternMatcher`.
+    pub fn matches_pattern_static(public_key: &str, pattern: &str, pattern_type: &PatternType, case_sensitive: bool) -> bool {
         let (key, pat) = if case_sensitive {
             (public_key.to_string(), pattern.to_string())
         } else {
@@ -215,27 +664,73 @@ impl VanityGenerator {
             PatternType::StartsWith => key.starts_with(&pat),
             PatternType::EndsWith => key.ends_with(&pat),
             PatternType::Contains => key.contains(&pat),
+            PatternType::Regex => pattern::compile_regex(pattern, case_sensitive)
+                .map(|regex| regex.is_match(public_key))
+                .unwrap_or(false),
+            PatternType::Glob => pattern::Glob::new(pattern, case_sensitive)
+                .map(|glob| glob.is_match(public_key))
+                .unwrap_or(false),
         }
     }
 
-    /// Estimate the probability of finding a vanity address
+    /// Estimate the probability of finding a vanity address matching any of the patterns
     pub fn estimate_probability(&self, options: &VanityOptions) -> f64 {
-        let alphabet_size: f64 = 58.0; // Base58 alphabet size
-        let pattern_length = options.pattern.len() as f64;
+        options
+            .patterns
+            .iter()
+            .map(|target| self.estimate_pattern_probability(&target.pattern, options))
+            .sum::<f64>()
+            .min(1.0)
+    }
 
-        let base_probability = 1.0 / alphabet_size.powf(pattern_length);
+    /// Estimate the probability of a single pattern (and any required suffix) matching
+    pub fn estimate_pattern_probability(&self, pattern: &str, options: &VanityOptions) -> f64 {
+        let probability = match options.pattern_type {
+            PatternType::Regex => pattern::estimate_regex_probability(pattern, options.case_sensitive),
+            PatternType::Glob => pattern::Glob::new(pattern, options.case_sensitive)
+                .map(|glob| glob.estimate_probability())
+                .unwrap_or(0.0),
+            PatternType::StartsWith => prefilter::prefix_probability(pattern, options.case_sensitive)
+                .unwrap_or_else(|| Self::literal_probability(pattern, options.case_sensitive)),
+            PatternType::EndsWith => Self::literal_probability(pattern, options.case_sensitive),
+            PatternType::Contains => {
+                // Any of the key's start positions may hold the pattern; treat them as independent
+                let positions = (pattern::BASE58_KEY_LENGTH + 1).saturating_sub(pattern.len());
+                let per_position = Self::literal_probability(pattern, options.case_sensitive);
+                1.0 - (1.0 - per_position).powi(positions as i32)
+            }
+        };
 
-        // Adjust for case sensitivity
-        if !options.case_sensitive {
-            // For case insensitive, we need to account for case variations
-            // This is a rough estimate - actual probability is higher
-            let case_variations = 2.0_f64.powf(pattern_length);
-            base_probability * case_variations.min(alphabet_size)
-        } else {
-            base_probability
+        // Prefix and suffix occupy disjoint positions, so the events are independent
+        match &options.suffix {
+            Some(suffix) => probability * Self::literal_probability(suffix, options.case_sensitive),
+            None => probability,
         }
     }
 
+    /// Probability of a literal pattern matching at one fixed position
+    ///
+    /// Each position accepts every Base58 spelling of its character: two for most
+    /// letters when case-insensitive, but only one for digits and for letters whose
+    /// other case is excluded from Base58 (`L`, `i`, `o`).
+    fn literal_probability(pattern: &str, case_sensitive: bool) -> f64 {
+        let alphabet = get_valid_base58_chars();
+        let alphabet_size = alphabet.len() as f64;
+
+        pattern
+            .chars()
+            .map(|c| {
+                let mut spellings = vec![c];
+                if !case_sensitive {
+                    spellings = vec![c.to_ascii_uppercase(), c.to_ascii_lowercase()];
+                    spellings.dedup();
+                }
+                let accepted = spellings.iter().filter(|&&spelling| alphabet.contains(spelling)).count();
+                accepted as f64 / alphabet_size
+            })
+            .product()
+    }
+
     /// Estimate expected number of attempts
     pub fn estimate_expected_attempts(&self, options: &VanityOptions) -> u64 {
         let probability = self.estimate_probability(options);
@@ -246,16 +741,93 @@ impl VanityGenerator {
         }
     }
 
-    /// Estimate expected time in seconds
-    pub fn estimate_expected_time(&self, options: &VanityOptions) -> Duration {
+    /// Estimate the expected time to find one address at the given total speed
+    pub fn estimate_expected_time(&self, options: &VanityOptions, attempts_per_second: f64) -> Duration {
         let expected_attempts = self.estimate_expected_attempts(options);
-        
-        // Conservative estimate: 50,000 attempts per second per thread
-        // This is much faster than the TypeScript version's ~2,000/sec
-        let attempts_per_second = 50_000.0;
         let seconds = expected_attempts as f64 / attempts_per_second;
-        
-        Duration::from_secs_f64(seconds)
+
+        Duration::try_from_secs_f64(seconds).unwrap_or(Duration::MAX)
+    }
+
+    /// Time within which one address is found with probability `quantile`, e.g. 0.9 for P90
+    ///
+    /// The attempts until the first hit follow a geometric distribution, so after `n`
+    /// attempts the chance of success is `1 - (1 - p)^n`.
+    pub fn estimate_time_quantile(&self, options: &VanityOptions, attempts_per_second: f64, quantile: f64) -> Duration {
+        let probability = self.estimate_probability(options);
+        let attempts = if probability >= 1.0 {
+            1.0
+        } else {
+            ((1.0 - quantile).ln() / (-probability).ln_1p()).ceil().max(1.0)
+        };
+
+        Duration::try_from_secs_f64(attempts / attempts_per_second).unwrap_or(Duration::MAX)
+    }
+
+    /// Time within which every pattern has its requested count with probability `quantile`
+    ///
+    /// Each pattern's hits arrive independently at a rate of `-ln(1 - p)` per attempt, so
+    /// after `n` attempts the chance that it has its `count` is a Poisson tail, and the run
+    /// is done when every pattern is; the rarest pattern dominates. Solved for `n` by
+    /// bisection. With one pattern and a count of one this is `estimate_time_quantile`.
+    pub fn estimate_completion_quantile(&self, options: &VanityOptions, attempts_per_second: f64, quantile: f64) -> Duration {
+        let targets: Vec<(f64, usize)> = options
+            .patterns
+            .iter()
+            .map(|target| {
+                let probability = self.estimate_pattern_probability(&target.pattern, options).min(1.0 - 1e-12);
+                (-(-probability).ln_1p(), target.count)
+            })
+            .collect();
+        if targets.iter().any(|&(rate, _)| rate <= 0.0) {
+            return Duration::MAX;
+        }
+        let all_done = |attempts: f64| -> f64 {
+            targets
+                .iter()
+                .map(|&(rate, count)| poisson_at_least(rate * attempts, count))
+                .product()
+        };
+
+        let mut high = targets
+            .iter()
+            .map(|&(rate, count)| count as f64 / rate)
+            .fold(1.0, f64::max);
+        while all_done(high) < quantile {
+            high *= 2.0;
+            if !high.is_finite() {
+                return Duration::MAX;
+            }
+        }
+        let mut low = 0.0;
+        while high - low > 1.0 && high - low > high * 1e-9 {
+            let middle = (low + high) / 2.0;
+            if all_done(middle) < quantile {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+
+        Duration::try_from_secs_f64(high.ceil().max(1.0) / attempts_per_second).unwrap_or(Duration::MAX)
+    }
+
+    /// Measure how many attempts per second a single worker thread manages for `options`
+    ///
+    /// Runs the same generate-and-match loop as a real search for about `duration`.
+    pub fn measure_attempt_rate(&self, options: &VanityOptions, duration: Duration) -> Result<f64> {
+        options.mode.validate()?;
+        let matcher = PatternMatcher::new(options)?;
+        let start_time = Instant::now();
+        let mut attempts = 0u64;
+
+        while start_time.elapsed() < duration {
+            let candidate = options.mode.generate(attempts);
+            attempts += 1;
+            std::hint::black_box(Self::match_candidate(&matcher, &candidate.pubkey()));
+        }
+
+        Ok(attempts as f64 / start_time.elapsed().as_secs_f64())
     }
 
     /// Format duration in a human-readable format
@@ -295,7 +867,6 @@ impl VanityGenerator {
 }
 
 /// Validate that a pattern only contains valid Base58 characters
-#[allow(dead_code)]
 pub fn is_valid_base58_pattern(pattern: &str) -> bool {
     // Base58 excludes: 0, O, I, l
     let invalid_chars = ['0', 'O', 'I', 'l'];
@@ -317,15 +888,56 @@ pub fn validate_base58_pattern(pattern: &str) -> Result<(), Vec<char>> {
     }
 }
 
+/// Check that a pattern of the given type can match a Base58 address
+pub fn validate_pattern(pattern: &str, pattern_type: &PatternType, case_sensitive: bool) -> Result<()> {
+    let message = match pattern_type {
+        PatternType::Regex => pattern::validate_regex_pattern(pattern, case_sensitive).err(),
+        PatternType::Glob => pattern::validate_glob_pattern(pattern, case_sensitive).err(),
+        _ => validate_base58_pattern(pattern).err().map(|invalid_chars| {
+            let invalid_chars: Vec<String> = invalid_chars.iter().map(|c| c.to_string()).collect();
+            format!("invalid Base58 characters: {}", invalid_chars.join(", "))
+        }),
+    };
+
+    match message {
+        Some(message) => Err(VanityError::InvalidPattern {
+            pattern: pattern.to_string(),
+            message,
+        }),
+        None => Ok(()),
+    }
+}
+
 /// Get all valid Base58 characters as a string
-#[allow(dead_code)]
 pub fn get_valid_base58_chars() -> &'static str {
     "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
 }
 
+/// Chance that a Poisson variable with mean `mean` is at least `count`
+fn poisson_at_least(mean: f64, count: usize) -> f64 {
+    if count == 0 {
+        return 1.0;
+    }
+    if mean <= 0.0 {
+        return 0.0;
+    }
+
+    // Sum the chances of fewer hits term by term in log space, as `e^-mean` alone can underflow
+    let mut ln_term = -mean;
+    let mut below = 0.0;
+    for k in 0..count {
+        if k > 0 {
+            ln_term += mean.ln() - (k as f64).ln();
+        }
+        below += ln_term.exp();
+    }
+    (1.0 - below).max(0.0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use solana_sdk::signature::{Keypair, Signer};
 
     #[test]
     fn test_pattern_matching() {
@@ -364,6 +976,26 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_regex_pattern_type() {
+        let public_key = "SoL42def456GHi789jkm";
+
+        assert!(VanityGenerator::matches_pattern_static(
+            public_key, "^(SoL|Dex)[1-9]{2}", &PatternType::Regex, true
+        ));
+        assert!(!VanityGenerator::matches_pattern_static(
+            public_key, "^(SOL|Dex)[1-9]{2}", &PatternType::Regex, true
+        ));
+        assert!(VanityGenerator::matches_pattern_static(
+            public_key, "^(SOL|Dex)[1-9]{2}", &PatternType::Regex, false
+        ));
+        assert!(VanityGenerator::matches_pattern_static(
+            public_key, "jkm$", &PatternType::Regex, true
+        ));
+
+        assert!(matches!("regex".parse::<PatternType>(), Ok(PatternType::Regex)));
+    }
+
     #[test]
     fn test_base58_validation() {
         assert!(is_valid_base58_pattern("ABC123"));
@@ -409,16 +1041,51 @@ mod tests {
         assert!(!valid_chars.contains('l'));
     }
 
+    #[test]
+    fn test_options_builder_validates() {
+        let options = VanityOptions::builder().pattern("AB", 2).pattern("z", 1).build().unwrap();
+        assert_eq!(options.patterns.len(), 2);
+        assert_eq!(options.patterns[0].count, 2);
+        assert!(!options.case_sensitive);
+        assert_eq!(options.max_time, Duration::from_secs(300));
+
+        assert!(matches!(VanityOptions::builder().build(), Err(VanityError::NoPatterns)));
+        assert!(matches!(
+            VanityOptions::builder().pattern("SOL", 1).build(),
+            Err(VanityError::InvalidPattern { pattern, .. }) if pattern == "SOL"
+        ));
+        assert!(matches!(
+            VanityOptions::builder().pattern("AB", 1).suffix("0x").build(),
+            Err(VanityError::InvalidPattern { pattern, .. }) if pattern == "0x"
+        ));
+        assert!(matches!(
+            VanityOptions::builder().pattern("AB", 0).build(),
+            Err(VanityError::InvalidPattern { pattern, .. }) if pattern == "AB"
+        ));
+        assert!(matches!(
+            VanityOptions::builder().pattern("Dev", 1).pattern_type(PatternType::EndsWith).suffix("Dev").build(),
+            Err(VanityError::InvalidOptions(_))
+        ));
+        assert!(matches!(
+            VanityOptions::builder().pattern("(unclosed", 1).pattern_type(PatternType::Regex).build(),
+            Err(VanityError::InvalidPattern { .. })
+        ));
+
+        let long_prefix = SearchMode::Seed {
+            base: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            seed_prefix: "x".repeat(32),
+        };
+        assert!(matches!(
+            VanityOptions::builder().pattern("AB", 1).mode(long_prefix).build(),
+            Err(VanityError::InvalidMode(_))
+        ));
+    }
+
     #[test]
     fn test_probability_estimation() {
         let generator = VanityGenerator::new();
-        let options = VanityOptions {
-            pattern: "A".to_string(),
-            pattern_type: PatternType::StartsWith,
-            case_sensitive: true,
-            max_attempts: 1000000,
-            max_time: Duration::from_secs(60),
-        };
+        let options = VanityOptions::builder().pattern("A", 1).case_sensitive(true).build().unwrap();
 
         let probability = generator.estimate_probability(&options);
         assert!(probability > 0.0);
@@ -427,5 +1094,249 @@ mod tests {
         let expected_attempts = generator.estimate_expected_attempts(&options);
         assert!(expected_attempts > 0);
         assert!(expected_attempts < 1000); // Should be around 58 for single character
+
+        // Completion times follow the geometric distribution of attempts
+        let speed = 1000.0;
+        let p50 = generator.estimate_time_quantile(&options, speed, 0.5);
+        let p90 = generator.estimate_time_quantile(&options, speed, 0.9);
+        let p99 = generator.estimate_time_quantile(&options, speed, 0.99);
+        let expected = generator.estimate_expected_time(&options, speed);
+        assert!(p50 < expected && expected < p90 && p90 < p99);
+        let median_attempts = (0.5f64.ln() / (1.0 - probability).ln()).ceil();
+        assert!((p50.as_secs_f64() - median_attempts / speed).abs() < 1e-9);
+
+        // Impossible patterns take forever rather than panicking
+        let impossible = VanityOptions {
+            patterns: vec![PatternTarget::new("0", 1)],
+            ..options.clone()
+        };
+        assert_eq!(generator.estimate_time_quantile(&impossible, speed, 0.5), Duration::MAX);
+        assert_eq!(generator.estimate_completion_quantile(&impossible, speed, 0.5), Duration::MAX);
+
+        // One address matches the single-hit quantile to within an attempt; more take longer, led by the rarest pattern
+        let one = generator.estimate_completion_quantile(&options, speed, 0.9).as_secs_f64();
+        assert!((one - p90.as_secs_f64()).abs() <= 1.0 / speed + 1e-9, "{} vs {:?}", one, p90);
+        let ten = VanityOptions {
+            patterns: vec![PatternTarget::new("A", 10)],
+            ..options.clone()
+        };
+        let ten_p50 = generator.estimate_completion_quantile(&ten, speed, 0.5).as_secs_f64();
+        let ten_expected = 10.0 / probability / speed;
+        assert!((ten_p50 - ten_expected).abs() < 0.1 * ten_expected, "{} vs {}", ten_p50, ten_expected);
+        let with_rare = VanityOptions {
+            patterns: vec![PatternTarget::new("A", 10), PatternTarget::new("zz", 1)],
+            ..options.clone()
+        };
+        assert!(generator.estimate_completion_quantile(&with_rare, speed, 0.5).as_secs_f64() > 20.0 * ten_p50);
+
+        // Several patterns are easier to hit than any one of them
+        let multi_options = VanityOptions {
+            patterns: vec![PatternTarget::new("A", 1), PatternTarget::new("z", 1)],
+            ..options.clone()
+        };
+        let multi_probability = generator.estimate_probability(&multi_options);
+        let z_probability = generator.estimate_pattern_probability("z", &options);
+        assert!((multi_probability - (probability + z_probability)).abs() < 1e-12);
+
+        // Prefixes use the real Base58 digit distribution: "z" leads far fewer keys than "A"
+        assert!(z_probability < probability / 50.0);
+
+        // A required suffix multiplies in its own probability
+        let suffix_options = VanityOptions {
+            suffix: Some("BC".to_string()),
+            ..options.clone()
+        };
+        let suffix_probability = generator.estimate_probability(&suffix_options);
+        assert!((suffix_probability - probability / (58.0 * 58.0)).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_case_insensitive_probability_per_character() {
+        let generator = VanityGenerator::new();
+        let options = VanityOptions::builder().pattern("x", 1).pattern_type(PatternType::EndsWith).build().unwrap();
+        let probability = |pattern: &str| generator.estimate_pattern_probability(pattern, &options);
+
+        // Digits and letters with an excluded other case have a single spelling
+        assert!((probability("x") - 2.0 / 58.0).abs() < 1e-15);
+        assert!((probability("o") - 1.0 / 58.0).abs() < 1e-15);
+        assert!((probability("L") - 1.0 / 58.0).abs() < 1e-15);
+        assert!((probability("7") - 1.0 / 58.0).abs() < 1e-15);
+        assert!((probability("ab9i") - 4.0 / 58f64.powi(4)).abs() < 1e-20);
+
+        // Estimated and measured hit rates agree over many random keys
+        let contains_options = VanityOptions { pattern_type: PatternType::Contains, ..options.clone() };
+        let contains_probability = |pattern: &str| generator.estimate_pattern_probability(pattern, &contains_options);
+        let samples = 20_000;
+        let suffixes = ["x", "o", "7"];
+        let substrings = ["x", "7", "ab"];
+        let mut suffix_hits = [0usize; 3];
+        let mut substring_hits = [0usize; 3];
+        for _ in 0..samples {
+            let public_key = Keypair::new().pubkey().to_string().to_lowercase();
+            for (suffix, hits) in suffixes.iter().zip(suffix_hits.iter_mut()) {
+                *hits += public_key.ends_with(suffix) as usize;
+            }
+            for (substring, hits) in substrings.iter().zip(substring_hits.iter_mut()) {
+                *hits += public_key.contains(substring) as usize;
+            }
+        }
+        for (suffix, hits) in suffixes.iter().zip(suffix_hits) {
+            let expected = probability(suffix) * samples as f64;
+            assert!((hits as f64 - expected).abs() < 0.25 * expected, "{}: {} hits, expected {}", suffix, hits, expected);
+        }
+        for (substring, hits) in substrings.iter().zip(substring_hits) {
+            let expected = contains_probability(substring) * samples as f64;
+            assert!((hits as f64 - expected).abs() < 0.25 * expected, "*{}*: {} hits, expected {}", substring, hits, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_multiple_per_pattern_counts() {
+        let generator = VanityGenerator::new();
+        let options = VanityOptions::builder().pattern("A", 2).pattern("B", 1).case_sensitive(true).build().unwrap();
+
+        let outcome = generator.generate_multiple_parallel(options, 2).await.unwrap();
+        assert_eq!(outcome.stop_reason, StopReason::Completed);
+        let results = outcome.results;
+        assert_eq!(results.iter().filter(|r| r.pattern == "A").count(), 2);
+        assert_eq!(results.iter().filter(|r| r.pattern == "B").count(), 1);
+        for result in &results {
+            assert!(result.public_key.starts_with(&result.pattern));
+        }
+    }
+
+    #[test]
+    fn test_generate_multiple_without_runtime() {
+        let generator = VanityGenerator::new().with_core_pinning(true);
+        let options = VanityOptions::builder()
+            .pattern("z", 2)
+            .pattern_type(PatternType::EndsWith)
+            .case_sensitive(true)
+            .build()
+            .unwrap();
+
+        let outcome = generator.generate_multiple(options, 3).unwrap();
+        assert_eq!(outcome.results.len(), 2);
+        assert_eq!(outcome.stop_reason, StopReason::Completed);
+        assert!(outcome.total_attempts >= 2);
+        assert!(outcome.results.iter().all(|r| r.public_key.ends_with('z')));
+        assert!(outcome.results.iter().all(|r| r.attempts <= outcome.total_attempts));
+    }
+
+    #[test]
+    fn test_result_handler_sees_every_result() {
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let handler_seen = Arc::clone(&seen);
+        let generator = VanityGenerator::new().with_result_handler(Arc::new(move |result| {
+            handler_seen.lock().unwrap().push(result.public_key.clone());
+        }));
+        let options = VanityOptions::builder().pattern("A", 3).case_sensitive(true).build().unwrap();
+
+        let outcome = generator.generate_multiple(options, 2).unwrap();
+        let public_keys: Vec<String> = outcome.results.iter().map(|r| r.public_key.clone()).collect();
+        assert_eq!(*seen.lock().unwrap(), public_keys);
+    }
+
+    #[test]
+    fn test_generate_stream_events() {
+        let options = VanityOptions::builder().pattern("A", 2).pattern("B", 1).build().unwrap();
+        let mut stream = VanityGenerator::new().generate_stream(options, 2).unwrap();
+
+        let mut found = Vec::new();
+        let mut finished = None;
+        while let Some(event) = stream.blocking_recv() {
+            match event {
+                SearchEvent::Found(result) => found.push(result),
+                SearchEvent::Progress(progress) => assert!(progress.found <= 3 && progress.requested == 3),
+                SearchEvent::Finished { total_attempts, stop_reason } => finished = Some((total_attempts, stop_reason)),
+            }
+        }
+
+        let (total_attempts, stop_reason) = finished.unwrap();
+        assert_eq!(stop_reason, StopReason::Completed);
+        assert_eq!(found.len(), 3);
+        assert!(found.iter().all(|r| r.attempts <= total_attempts));
+        assert_eq!(found.iter().filter(|r| r.pattern == "A").count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_stream_leaves_generator_usable() {
+        let generator = VanityGenerator::new();
+        let hopeless = VanityOptions::builder().pattern("zzzzzzzz", 1).max_attempts(u64::MAX).build().unwrap();
+        let mut stream = generator.generate_stream(hopeless, 2).unwrap();
+
+        // Progress keeps arriving until the stream is cancelled
+        assert!(matches!(stream.recv().await, Some(SearchEvent::Progress(_))));
+        stream.cancel();
+        let mut last = None;
+        while let Some(event) = stream.recv().await {
+            last = Some(event);
+        }
+        assert!(matches!(last, Some(SearchEvent::Finished { stop_reason: StopReason::Interrupted, .. })));
+
+        // Only that search stopped; the generator can still run others
+        let options = VanityOptions::builder().pattern("A", 1).build().unwrap();
+        let outcome = generator.generate_multiple(options, 2).unwrap();
+        assert_eq!(outcome.stop_reason, StopReason::Completed);
+    }
+
+    #[test]
+    fn test_measure_attempt_rate() {
+        let options = VanityOptions::builder().pattern("AB", 1).case_sensitive(true).build().unwrap();
+
+        let rate = VanityGenerator::new().measure_attempt_rate(&options, Duration::from_millis(100)).unwrap();
+        assert!(rate > 0.0);
+    }
+
+    #[test]
+    fn test_generate_multiple_attempt_limit() {
+        let generator = VanityGenerator::new();
+        // Practically impossible, so only the attempt budget can end the search
+        let options = VanityOptions::builder().pattern("zzzzzzzz", 1).max_attempts(2_500).build().unwrap();
+
+        let outcome = generator.generate_multiple(options, 4).unwrap();
+        assert_eq!(outcome.stop_reason, StopReason::AttemptLimit);
+        assert_eq!(outcome.total_attempts, 2_500);
+        assert!(outcome.results.is_empty());
+    }
+
+    #[test]
+    fn test_cancel_stops_workers_and_keeps_results() {
+        let generator = VanityGenerator::new();
+        // One easy pattern we can find, one we never will
+        let options = VanityOptions::builder()
+            .pattern("A", 1)
+            .pattern("zzzzzzzz", 1)
+            .case_sensitive(true)
+            .max_attempts(u64::MAX)
+            .build()
+            .unwrap();
+
+        let canceller = generator.clone();
+        let cancel_thread = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(500));
+            canceller.cancel();
+        });
+
+        let outcome = generator.generate_multiple(options, 2).unwrap();
+        cancel_thread.join().unwrap();
+        assert_eq!(outcome.stop_reason, StopReason::Interrupted);
+        assert_eq!(outcome.results.len(), 1);
+        assert_eq!(outcome.results[0].pattern, "A");
+    }
+
+    #[test]
+    fn test_generate_multiple_time_limit() {
+        let generator = VanityGenerator::new();
+        let options = VanityOptions::builder()
+            .pattern("zzzzzzzz", 1)
+            .max_attempts(u64::MAX)
+            .max_time(Duration::from_millis(200))
+            .build()
+            .unwrap();
+
+        let outcome = generator.generate_multiple(options, 2).unwrap();
+        assert_eq!(outcome.stop_reason, StopReason::TimeLimit);
+        assert!(outcome.total_attempts > 0);
     }
 }